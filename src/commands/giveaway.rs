@@ -1,13 +1,73 @@
 use poise::serenity_prelude as serenity;
 use crate::{Context, Error};
+use crate::giveaway_system::{events, history, requirements};
+use crate::giveaway_system::structs::NewGiveaway;
+use crate::duration::parse_deadline;
 
-#[poise::command(slash_command, guild_only)]
-pub async fn giveaway(ctx: Context<'_>) -> Result<(), Error> {
+async fn ensure_staff(ctx: Context<'_>) -> Result<bool, Error> {
     let staff_role = ctx.data().config.roles.staff_role_id;
     let has_role = ctx.author().has_role(ctx.http(), ctx.guild_id().unwrap(), serenity::RoleId::new(staff_role)).await?;
-    
+
     if !has_role {
         ctx.send(poise::CreateReply::default().content("Vous n'avez pas la permission.").ephemeral(true)).await?;
+    }
+
+    Ok(has_role)
+}
+
+async fn giveaway_choices(ctx: Context<'_>, partial: &str, status: &str) -> Vec<serenity::AutocompleteChoice> {
+    let partial = partial.to_lowercase();
+
+    let giveaways: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT message_id, title FROM giveaways WHERE status = ? ORDER BY end_time DESC"
+    )
+    .bind(status)
+    .fetch_all(&ctx.data().db)
+    .await
+    .unwrap_or_default();
+
+    giveaways.into_iter()
+        .filter(|(_, title)| title.to_lowercase().contains(&partial))
+        .take(25)
+        .map(|(message_id, title)| serenity::AutocompleteChoice::new(title.chars().take(100).collect::<String>(), message_id.to_string()))
+        .collect()
+}
+
+async fn autocomplete_giveaway(
+    ctx: Context<'_>,
+    partial: &str,
+) -> Vec<serenity::AutocompleteChoice> {
+    giveaway_choices(ctx, partial, "active").await
+}
+
+async fn autocomplete_ended_giveaway(
+    ctx: Context<'_>,
+    partial: &str,
+) -> Vec<serenity::AutocompleteChoice> {
+    giveaway_choices(ctx, partial, "ended").await
+}
+
+async fn find_giveaway(ctx: Context<'_>, giveaway: &str) -> Result<Option<(i64, String)>, Error> {
+    let Ok(message_id) = giveaway.trim().parse::<i64>() else {
+        return Ok(None);
+    };
+
+    let found = sqlx::query_as("SELECT message_id, title FROM giveaways WHERE message_id = ?")
+        .bind(message_id)
+        .fetch_optional(&ctx.data().db)
+        .await?;
+
+    Ok(found)
+}
+
+#[poise::command(slash_command, guild_only, subcommands("giveaway_panel", "giveaway_start", "giveaway_requirements", "giveaway_bonus", "giveaway_end", "giveaway_reroll", "giveaway_history", "giveaway_wins"))]
+pub async fn giveaway(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, rename = "panel")]
+pub async fn giveaway_panel(ctx: Context<'_>) -> Result<(), Error> {
+    if !ensure_staff(ctx).await? {
         return Ok(());
     }
 
@@ -18,7 +78,7 @@ pub async fn giveaway(ctx: Context<'_>) -> Result<(), Error> {
 
     let buttons = vec![
         serenity::CreateButton::new("giveaway_create_btn").label("Create").style(serenity::ButtonStyle::Success),
-        serenity::CreateButton::new("giveaway_delete_btn").label("Delete").style(serenity::ButtonStyle::Danger),
+        serenity::CreateButton::new("giveaway_manage_open").label("Manage").style(serenity::ButtonStyle::Secondary),
     ];
 
     ctx.send(poise::CreateReply::default()
@@ -29,3 +89,368 @@ pub async fn giveaway(ctx: Context<'_>) -> Result<(), Error> {
 
     Ok(())
 }
+
+#[poise::command(slash_command, guild_only, rename = "start")]
+#[allow(clippy::too_many_arguments)]
+pub async fn giveaway_start(
+    ctx: Context<'_>,
+    #[description = "Récompense à gagner"] reward: String,
+    #[description = "Durée (ex: 1j2h30m, 2sem) ou date de fin (ex: 25/12/2026 18:00, heure UTC par défaut)"] duration: String,
+    #[description = "Nombre de gagnants (1 par défaut)"]
+    #[min = 1]
+    #[max = 20]
+    winners: Option<u32>,
+    #[description = "Salon du giveaway (salon actuel par défaut)"]
+    #[channel_types("Text", "News")]
+    channel: Option<serenity::GuildChannel>,
+    #[description = "Titre (la récompense par défaut)"] title: Option<String>,
+    #[description = "Description du giveaway"] description: Option<String>,
+    #[description = "Début programmé : délai (ex: 2h) ou date (ex: 24/12/2026 20:00, UTC par défaut)"] start_at: Option<String>,
+    #[description = "Rôle à mentionner au lancement"] ping_role: Option<serenity::Role>,
+) -> Result<(), Error> {
+    if !ensure_staff(ctx).await? {
+        return Ok(());
+    }
+
+    let data = ctx.data();
+
+    let now = chrono::Utc::now().timestamp();
+    let start_at = match start_at.as_deref().map(|s| parse_deadline(s, now)).transpose() {
+        Ok(start_at) => start_at,
+        Err(why) => {
+            ctx.send(poise::CreateReply::default().content(format!("❌ Début : {}", why)).ephemeral(true)).await?;
+            return Ok(());
+        }
+    };
+
+    let start = start_at.unwrap_or(now);
+    let duration_secs = match events::giveaway_end_time(&duration, start) {
+        Ok(end_time) => end_time - start,
+        Err(why) => {
+            ctx.send(poise::CreateReply::default().content(format!("❌ Durée : {}", why)).ephemeral(true)).await?;
+            return Ok(());
+        }
+    };
+
+    let giveaway = NewGiveaway {
+        channel_id: channel.map(|c| c.id).unwrap_or(ctx.channel_id()),
+        host_id: ctx.author().id,
+        title: title.unwrap_or_else(|| reward.clone()),
+        description: description.unwrap_or_default(),
+        reward,
+        winner_count: winners.unwrap_or(1) as i32,
+        duration_secs,
+        ping_role_id: ping_role.map(|r| r.id),
+    };
+
+    let content = match start_at {
+        Some(start_at) => {
+            events::schedule_giveaway(ctx.serenity_context(), data, &giveaway, start_at).await?;
+            format!("Giveaway **{}** programmé dans <#{}> pour <t:{}:f>.", giveaway.title, giveaway.channel_id, start_at)
+        }
+        None => {
+            let message = events::launch_giveaway(ctx.serenity_context(), data, &giveaway).await?;
+            format!("Giveaway **{}** lancé : {}", giveaway.title, message.link())
+        }
+    };
+
+    ctx.send(poise::CreateReply::default().content(content).ephemeral(true)).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, rename = "requirements")]
+#[allow(clippy::too_many_arguments)]
+pub async fn giveaway_requirements(
+    ctx: Context<'_>,
+    #[description = "Giveaway concerné"]
+    #[autocomplete = "autocomplete_giveaway"]
+    giveaway: String,
+    #[description = "Rôle obligatoire pour participer"] required_role: Option<serenity::Role>,
+    #[description = "Rôle exclu du giveaway"] forbidden_role: Option<serenity::Role>,
+    #[description = "Ancienneté minimale sur le serveur, en jours (0 pour retirer)"] min_member_days: Option<u32>,
+    #[description = "Âge minimal du compte, en jours (0 pour retirer)"] min_account_days: Option<u32>,
+    #[description = "Temps vocal minimal, en minutes (0 pour retirer)"] min_voice_minutes: Option<u32>,
+    #[description = "Nombre minimal de messages (0 pour retirer)"] min_messages: Option<u32>,
+    #[description = "Supprimer toutes les conditions existantes avant d'appliquer les nouvelles"] reset: Option<bool>,
+) -> Result<(), Error> {
+    if !ensure_staff(ctx).await? {
+        return Ok(());
+    }
+
+    let data = ctx.data();
+
+    let Some((message_id, title)) = find_giveaway(ctx, &giveaway).await? else {
+        ctx.send(poise::CreateReply::default().content("Giveaway introuvable.").ephemeral(true)).await?;
+        return Ok(());
+    };
+
+    if reset.unwrap_or(false) {
+        sqlx::query(
+            "UPDATE giveaways SET min_member_days = NULL, min_account_days = NULL, min_voice_minutes = NULL, min_messages = NULL
+             WHERE message_id = ?"
+        )
+        .bind(message_id)
+        .execute(&data.db)
+        .await?;
+
+        sqlx::query("DELETE FROM giveaway_roles WHERE giveaway_message_id = ?")
+            .bind(message_id)
+            .execute(&data.db)
+            .await?;
+    }
+
+    let thresholds = [
+        ("min_member_days", min_member_days),
+        ("min_account_days", min_account_days),
+        ("min_voice_minutes", min_voice_minutes),
+        ("min_messages", min_messages),
+    ];
+
+    for (column, value) in thresholds {
+        if let Some(value) = value {
+            sqlx::query(&format!("UPDATE giveaways SET {} = ? WHERE message_id = ?", column))
+                .bind((value > 0).then_some(value as i64))
+                .bind(message_id)
+                .execute(&data.db)
+                .await?;
+        }
+    }
+
+    for (role, required) in [(required_role, true), (forbidden_role, false)] {
+        if let Some(role) = role {
+            sqlx::query("INSERT OR REPLACE INTO giveaway_roles (giveaway_message_id, role_id, required) VALUES (?, ?, ?)")
+                .bind(message_id)
+                .bind(role.id.get() as i64)
+                .bind(required)
+                .execute(&data.db)
+                .await?;
+        }
+    }
+
+    let current = requirements::load(&data.db, message_id).await?;
+    let roles = requirements::load_roles(&data.db, message_id).await?;
+
+    let mut lines = Vec::new();
+    for (role_id, required) in roles {
+        if required {
+            lines.push(format!("• Rôle requis : <@&{}>", role_id));
+        } else {
+            lines.push(format!("• Rôle exclu : <@&{}>", role_id));
+        }
+    }
+    if let Some(days) = current.min_member_days {
+        lines.push(format!("• Membre depuis au moins {} jour(s)", days));
+    }
+    if let Some(days) = current.min_account_days {
+        lines.push(format!("• Compte créé depuis au moins {} jour(s)", days));
+    }
+    if let Some(minutes) = current.min_voice_minutes {
+        lines.push(format!("• Au moins {} en vocal", crate::voice_system::activity::format_duration(minutes * 60)));
+    }
+    if let Some(count) = current.min_messages {
+        lines.push(format!("• Au moins {} messages envoyés", count));
+    }
+
+    let description = if lines.is_empty() {
+        "Aucune condition : tout le monde peut participer.".to_string()
+    } else {
+        lines.join("\n")
+    };
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("Conditions : {}", title))
+        .description(description)
+        .color(0x3498db);
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true)).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, rename = "bonus")]
+pub async fn giveaway_bonus(
+    ctx: Context<'_>,
+    #[description = "Giveaway concerné"]
+    #[autocomplete = "autocomplete_giveaway"]
+    giveaway: String,
+    #[description = "Rôle donnant des entrées bonus"] role: serenity::Role,
+    #[description = "Nombre d'entrées pour ce rôle (1 pour retirer le bonus)"]
+    #[min = 1]
+    #[max = 10]
+    entries: u32,
+) -> Result<(), Error> {
+    if !ensure_staff(ctx).await? {
+        return Ok(());
+    }
+
+    let data = ctx.data();
+
+    let Some((message_id, title)) = find_giveaway(ctx, &giveaway).await? else {
+        ctx.send(poise::CreateReply::default().content("Giveaway introuvable.").ephemeral(true)).await?;
+        return Ok(());
+    };
+
+    if entries > 1 {
+        sqlx::query("INSERT OR REPLACE INTO giveaway_bonus_roles (giveaway_message_id, role_id, entries) VALUES (?, ?, ?)")
+            .bind(message_id)
+            .bind(role.id.get() as i64)
+            .bind(entries as i64)
+            .execute(&data.db)
+            .await?;
+    } else {
+        sqlx::query("DELETE FROM giveaway_bonus_roles WHERE giveaway_message_id = ? AND role_id = ?")
+            .bind(message_id)
+            .bind(role.id.get() as i64)
+            .execute(&data.db)
+            .await?;
+    }
+
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap();
+    let participants: Vec<i64> = sqlx::query_scalar("SELECT user_id FROM giveaway_participants WHERE giveaway_message_id = ?")
+        .bind(message_id)
+        .fetch_all(&data.db)
+        .await?;
+
+    for user_id in participants {
+        let Ok(member) = guild_id.member(ctx, serenity::UserId::new(user_id as u64)).await else { continue };
+        let weight = requirements::entry_weight(&data.db, message_id, &member).await?;
+
+        sqlx::query("UPDATE giveaway_participants SET weight = ? WHERE giveaway_message_id = ? AND user_id = ?")
+            .bind(weight)
+            .bind(message_id)
+            .bind(user_id)
+            .execute(&data.db)
+            .await?;
+    }
+
+    let bonuses = requirements::load_bonus_roles(&data.db, message_id).await?;
+    let description = if bonuses.is_empty() {
+        "Aucun bonus : chaque participant dispose d'une entrée.".to_string()
+    } else {
+        bonuses.iter()
+            .map(|(role_id, entries)| format!("• <@&{}> : ×{}", role_id, entries))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("Entrées bonus : {}", title))
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new("Un membre avec plusieurs rôles bonus reçoit le bonus le plus élevé."))
+        .color(0x3498db);
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true)).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, rename = "end")]
+pub async fn giveaway_end(
+    ctx: Context<'_>,
+    #[description = "Giveaway à terminer"]
+    #[autocomplete = "autocomplete_giveaway"]
+    giveaway: String,
+) -> Result<(), Error> {
+    if !ensure_staff(ctx).await? {
+        return Ok(());
+    }
+
+    let data = ctx.data();
+
+    let Some((message_id, title)) = find_giveaway(ctx, &giveaway).await? else {
+        ctx.send(poise::CreateReply::default().content("Giveaway introuvable.").ephemeral(true)).await?;
+        return Ok(());
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let ended = events::end_giveaway(ctx.serenity_context(), data, message_id, Some(ctx.author().id)).await?;
+
+    let content = if ended {
+        format!("Le giveaway **{}** a été terminé.", title)
+    } else {
+        format!("Le giveaway **{}** n'est pas en cours.", title)
+    };
+
+    ctx.send(poise::CreateReply::default().content(content).ephemeral(true)).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, rename = "reroll")]
+pub async fn giveaway_reroll(
+    ctx: Context<'_>,
+    #[description = "Giveaway terminé à relancer"]
+    #[autocomplete = "autocomplete_ended_giveaway"]
+    message_id: String,
+    #[description = "Nombre de nouveaux gagnants (1 par défaut)"]
+    #[min = 1]
+    #[max = 20]
+    count: Option<u32>,
+) -> Result<(), Error> {
+    if !ensure_staff(ctx).await? {
+        return Ok(());
+    }
+
+    let data = ctx.data();
+
+    let Some((message_id, title)) = find_giveaway(ctx, &message_id).await? else {
+        ctx.send(poise::CreateReply::default().content("Giveaway introuvable.").ephemeral(true)).await?;
+        return Ok(());
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let winners = events::reroll_giveaway(
+        ctx.serenity_context(),
+        data,
+        message_id,
+        count.unwrap_or(1) as usize,
+        Some(ctx.author().id),
+        &[],
+    ).await?;
+
+    let content = if winners.is_empty() {
+        format!("Aucun nouveau gagnant possible pour **{}** (giveaway non terminé ou plus aucun participant éligible).", title)
+    } else {
+        let mentions: Vec<String> = winners.iter().map(|id| format!("<@{}>", id)).collect();
+        format!("Nouveau tirage pour **{}** : {}", title, mentions.join(", "))
+    };
+
+    ctx.send(poise::CreateReply::default().content(content).ephemeral(true)).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, rename = "history")]
+pub async fn giveaway_history(ctx: Context<'_>) -> Result<(), Error> {
+    if !ensure_staff(ctx).await? {
+        return Ok(());
+    }
+
+    let (embed, components) = history::history_page(&ctx.data().db, ctx.guild_id(), 0).await?;
+
+    ctx.send(poise::CreateReply::default().embed(embed).components(components).ephemeral(true)).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, rename = "wins")]
+pub async fn giveaway_wins(
+    ctx: Context<'_>,
+    #[description = "Membre à consulter"]
+    user: serenity::User,
+) -> Result<(), Error> {
+    if !ensure_staff(ctx).await? {
+        return Ok(());
+    }
+
+    let embed = history::wins_embed(&ctx.data().db, &user).await?;
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true)).await?;
+
+    Ok(())
+}
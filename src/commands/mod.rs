@@ -4,3 +4,6 @@ pub mod ticket;
 pub mod moderation;
 pub mod profile;
 pub mod giveaway;
+pub mod panel;
+pub mod pagination;
+pub mod voice;
@@ -0,0 +1,66 @@
+use crate::{Context, Error};
+use poise::serenity_prelude as serenity;
+
+pub struct Page {
+    pub embed: serenity::CreateEmbed,
+    pub buttons: Vec<serenity::CreateButton>,
+}
+
+fn page_components(page: &Page, index: usize, total: usize, ctx_id: u64) -> Vec<serenity::CreateActionRow> {
+    let mut rows = Vec::new();
+
+    if !page.buttons.is_empty() {
+        rows.push(serenity::CreateActionRow::Buttons(page.buttons.clone()));
+    }
+
+    if total > 1 {
+        rows.push(serenity::CreateActionRow::Buttons(vec![
+            serenity::CreateButton::new(format!("{}prev", ctx_id)).emoji('◀').style(serenity::ButtonStyle::Secondary),
+            serenity::CreateButton::new(format!("{}page", ctx_id)).label(format!("{}/{}", index + 1, total)).style(serenity::ButtonStyle::Secondary).disabled(true),
+            serenity::CreateButton::new(format!("{}next", ctx_id)).emoji('▶').style(serenity::ButtonStyle::Secondary),
+        ]));
+    }
+
+    rows
+}
+
+pub async fn paginate(ctx: Context<'_>, pages: Vec<Page>) -> Result<(), Error> {
+    if pages.is_empty() {
+        return Ok(());
+    }
+
+    let ctx_id = ctx.id();
+    let mut current = 0;
+
+    ctx.send(poise::CreateReply::default()
+        .embed(pages[current].embed.clone())
+        .components(page_components(&pages[current], current, pages.len(), ctx_id))
+        .ephemeral(true)
+    ).await?;
+
+    if pages.len() == 1 {
+        return Ok(());
+    }
+
+    while let Some(press) = serenity::ComponentInteractionCollector::new(ctx)
+        .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+        .timeout(std::time::Duration::from_secs(600))
+        .await
+    {
+        if press.data.custom_id.ends_with("next") {
+            current = (current + 1) % pages.len();
+        } else if press.data.custom_id.ends_with("prev") {
+            current = current.checked_sub(1).unwrap_or(pages.len() - 1);
+        } else {
+            continue;
+        }
+
+        press.create_response(ctx, serenity::CreateInteractionResponse::UpdateMessage(
+            serenity::CreateInteractionResponseMessage::new()
+                .embed(pages[current].embed.clone())
+                .components(page_components(&pages[current], current, pages.len(), ctx_id))
+        )).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::{Context, Error};
+use poise::serenity_prelude as serenity;
+
+#[poise::command(slash_command, guild_only, subcommands("send"))]
+pub async fn panel(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn send(
+    ctx: Context<'_>,
+    #[description = "Salon où publier le panneau (par défaut : salon actuel)"]
+    #[channel_types("Text")]
+    channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    let data = ctx.data();
+
+    let has_role = if let Some(member) = ctx.author_member().await {
+        member.roles.contains(&serenity::RoleId::new(data.config.roles.staff_role_id))
+    } else {
+        false
+    };
+
+    if !has_role {
+        ctx.send(poise::CreateReply::default()
+            .content("Vous n'avez pas la permission d'utiliser cette commande.")
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    }
+
+    let target = channel.map(|c| c.id).unwrap_or(ctx.channel_id());
+
+    let embed = serenity::CreateEmbed::new()
+        .title("Support VECTOR © Store")
+        .description("Besoin d'aide ? Choisissez une catégorie ci-dessous pour ouvrir un ticket.\nNeed help? Pick a category below to open a ticket.\n\nVous pouvez aussi nous écrire en message privé.")
+        .color(0x5865F2)
+        .footer(serenity::CreateEmbedFooter::new("VECTOR ©"));
+
+    let buttons = vec![
+        serenity::CreateButton::new("panel_cat_partnership").label("Partenariat / Partnership").style(serenity::ButtonStyle::Primary),
+        serenity::CreateButton::new("panel_cat_recruitment").label("Recrutement / Recruitment").style(serenity::ButtonStyle::Primary),
+        serenity::CreateButton::new("panel_cat_support").label("Support").style(serenity::ButtonStyle::Success),
+        serenity::CreateButton::new("panel_cat_other").label("Autres / Other").style(serenity::ButtonStyle::Secondary),
+    ];
+
+    target.send_message(&ctx, serenity::CreateMessage::new()
+        .embed(embed)
+        .components(vec![serenity::CreateActionRow::Buttons(buttons)])
+    ).await?;
+
+    ctx.send(poise::CreateReply::default()
+        .content(format!("Panneau de support publié dans <#{}>.", target))
+        .ephemeral(true)
+    ).await?;
+
+    Ok(())
+}
@@ -2,7 +2,21 @@ use crate::{Context, Error};
 use poise::serenity_prelude as serenity;
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
-use crate::ticket_system::structs::TicketInfo;
+use crate::ticket_system::structs::{TicketInfo, TicketCategory};
+use crate::commands::pagination::{self, Page};
+use crate::duration::{check_bounds, parse_duration};
+
+const TICKETS_PER_PAGE: usize = 5;
+const MIN_REMINDER_SECS: i64 = 60;
+const MAX_REMINDER_SECS: i64 = 30 * 86400;
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum WaitingOn {
+    #[name = "Staff"]
+    Staff,
+    #[name = "Utilisateur"]
+    User,
+}
 
 #[poise::command(slash_command, guild_only)]
 pub async fn rep(
@@ -26,6 +40,14 @@ pub async fn rep(
         
         let content = format!("**Support**: {}", message);
         dm_channel.say(&ctx, content).await?;
+
+        crate::ticket_system::events::mark_answered(ctx.http(), &data.db, ticket.user_id as u64).await?;
+
+        sqlx::query("UPDATE tickets SET awaiting_staff = 0, assigned_to = COALESCE(assigned_to, ?) WHERE user_id = ?")
+            .bind(ctx.author().id.get() as i64)
+            .bind(ticket.user_id)
+            .execute(&data.db)
+            .await?;
         
         ctx.send(poise::CreateReply::default()
             .content(format!("Message envoyé à <@{}> : {}", ticket.user_id, message))
@@ -57,10 +79,7 @@ pub async fn close(ctx: Context<'_>) -> Result<(), Error> {
     .await?;
 
     if let Some(ticket) = ticket {
-        sqlx::query("DELETE FROM tickets WHERE user_id = ?")
-            .bind(ticket.user_id)
-            .execute(&data.db)
-            .await?;
+        crate::ticket_system::events::delete_ticket_records(&data.db, ticket.user_id, ticket.channel_id).await?;
 
         ctx.defer().await?;
 
@@ -76,7 +95,7 @@ pub async fn close(ctx: Context<'_>) -> Result<(), Error> {
         let close_date = chrono::Local::now().format("%d/%m/%Y %H:%M:%S").to_string();
 
         let mut transcript = String::new();
-        transcript.push_str(&format!("=== TRANSCRIPT TICKET ===\n"));
+        transcript.push_str("=== TRANSCRIPT TICKET ===\n");
         transcript.push_str(&format!("Utilisateur : {} (ID: {})\n", user_id, ticket.user_id));
         transcript.push_str(&format!("Catégorie : {}\n", ticket.category));
         transcript.push_str(&format!("Ouvert le : {}\n", open_date));
@@ -171,3 +190,306 @@ pub async fn rename(
 
     Ok(())
 }
+
+#[poise::command(slash_command, guild_only)]
+pub async fn remind(
+    ctx: Context<'_>,
+    #[description = "Délai avant le rappel (ex: 1j, 2h, 30m)"] r#in: String,
+    #[description = "Le rappel à afficher"] message: String,
+) -> Result<(), Error> {
+    schedule_ticket_reminder(ctx, "remind", &r#in, message).await
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn followup(
+    ctx: Context<'_>,
+    #[description = "Délai avant l'envoi du suivi (ex: 1j, 2h, 30m)"] r#in: String,
+    #[description = "Message de suivi à envoyer à l'utilisateur"] message: Option<String>,
+) -> Result<(), Error> {
+    let message = message.unwrap_or("Votre problème est-il résolu ? N'hésitez pas à répondre à ce message si vous avez encore besoin d'aide.".to_string());
+    schedule_ticket_reminder(ctx, "followup", &r#in, message).await
+}
+
+async fn schedule_ticket_reminder(
+    ctx: Context<'_>,
+    kind: &str,
+    delay: &str,
+    message: String,
+) -> Result<(), Error> {
+    let data = ctx.data();
+    let channel_id = ctx.channel_id();
+
+    let ticket: Option<TicketInfo> = sqlx::query_as(
+        "SELECT * FROM tickets WHERE channel_id = ?"
+    )
+    .bind(channel_id.get() as i64)
+    .fetch_optional(&data.db)
+    .await?;
+
+    let Some(ticket) = ticket else {
+        ctx.send(poise::CreateReply::default()
+            .content("Ce salon n'est pas un ticket actif.")
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    };
+
+    let seconds = match parse_duration(delay).and_then(|seconds| check_bounds(seconds, MIN_REMINDER_SECS, MAX_REMINDER_SECS)) {
+        Ok(seconds) => seconds,
+        Err(why) => {
+            ctx.send(poise::CreateReply::default()
+                .content(format!("❌ {}", why))
+                .ephemeral(true)
+            ).await?;
+            return Ok(());
+        }
+    };
+
+    let due_at = chrono::Utc::now().timestamp() + seconds;
+
+    sqlx::query(
+        "INSERT INTO ticket_reminders (kind, channel_id, user_id, staff_id, message, due_at) VALUES (?, ?, ?, ?, ?, ?)"
+    )
+    .bind(kind)
+    .bind(channel_id.get() as i64)
+    .bind(ticket.user_id)
+    .bind(ctx.author().id.get() as i64)
+    .bind(&message)
+    .bind(due_at)
+    .execute(&data.db)
+    .await?;
+
+    let content = if kind == "followup" {
+        format!("Suivi programmé pour <@{}> <t:{}:R>.", ticket.user_id, due_at)
+    } else {
+        format!("Rappel programmé <t:{}:R>.", due_at)
+    };
+
+    ctx.send(poise::CreateReply::default().content(content).ephemeral(true)).await?;
+
+    Ok(())
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase().chars().take(32).collect()
+}
+
+async fn autocomplete_tag(
+    ctx: Context<'_>,
+    partial: &str,
+) -> Vec<String> {
+    let data = ctx.data();
+    let partial = partial.to_lowercase();
+
+    let mut tags = data.config.tickets.predefined_tags.clone();
+    let used: Vec<String> = sqlx::query_scalar("SELECT DISTINCT tag FROM ticket_tags")
+        .fetch_all(&data.db)
+        .await
+        .unwrap_or_default();
+
+    for tag in used {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    tags.into_iter()
+        .filter(|t| t.contains(&partial))
+        .take(25)
+        .collect()
+}
+
+#[poise::command(slash_command, guild_only, subcommands("tag_add", "tag_remove"))]
+pub async fn tag(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn is_ticket_channel(db: &sqlx::Pool<sqlx::Sqlite>, channel_id: serenity::ChannelId) -> Result<bool, Error> {
+    let is_ticket = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM tickets WHERE channel_id = ?)")
+        .bind(channel_id.get() as i64)
+        .fetch_one(db)
+        .await?;
+
+    Ok(is_ticket)
+}
+
+#[poise::command(slash_command, guild_only, rename = "add")]
+pub async fn tag_add(
+    ctx: Context<'_>,
+    #[description = "Tag à ajouter au ticket"]
+    #[autocomplete = "autocomplete_tag"]
+    tag: String,
+) -> Result<(), Error> {
+    let data = ctx.data();
+    let channel_id = ctx.channel_id();
+    let tag = normalize_tag(&tag);
+
+    if !is_ticket_channel(&data.db, channel_id).await? || tag.is_empty() {
+        ctx.send(poise::CreateReply::default()
+            .content("Ce salon n'est pas un ticket actif.")
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    }
+
+    sqlx::query("INSERT OR IGNORE INTO ticket_tags (channel_id, tag) VALUES (?, ?)")
+        .bind(channel_id.get() as i64)
+        .bind(&tag)
+        .execute(&data.db)
+        .await?;
+
+    ctx.send(poise::CreateReply::default()
+        .content(format!("Tag `{}` ajouté au ticket.", tag))
+        .ephemeral(true)
+    ).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, rename = "remove")]
+pub async fn tag_remove(
+    ctx: Context<'_>,
+    #[description = "Tag à retirer du ticket"]
+    #[autocomplete = "autocomplete_tag"]
+    tag: String,
+) -> Result<(), Error> {
+    let data = ctx.data();
+    let channel_id = ctx.channel_id();
+    let tag = normalize_tag(&tag);
+
+    if !is_ticket_channel(&data.db, channel_id).await? {
+        ctx.send(poise::CreateReply::default()
+            .content("Ce salon n'est pas un ticket actif.")
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    }
+
+    let result = sqlx::query("DELETE FROM ticket_tags WHERE channel_id = ? AND tag = ?")
+        .bind(channel_id.get() as i64)
+        .bind(&tag)
+        .execute(&data.db)
+        .await?;
+
+    let content = if result.rows_affected() > 0 {
+        format!("Tag `{}` retiré du ticket.", tag)
+    } else {
+        format!("Ce ticket n'a pas le tag `{}`.", tag)
+    };
+
+    ctx.send(poise::CreateReply::default().content(content).ephemeral(true)).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, subcommands("list"))]
+pub async fn tickets(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "Filtrer par catégorie"] category: Option<TicketCategory>,
+    #[description = "Filtrer par tag"]
+    #[autocomplete = "autocomplete_tag"]
+    tag: Option<String>,
+    #[description = "Filtrer par staff assigné"] assignee: Option<serenity::User>,
+    #[description = "Ouverts depuis au moins (heures)"] min_age_hours: Option<u32>,
+    #[description = "En attente de réponse de"] waiting_on: Option<WaitingOn>,
+) -> Result<(), Error> {
+    let data = ctx.data();
+
+    let has_role = if let Some(member) = ctx.author_member().await {
+        member.roles.contains(&serenity::RoleId::new(data.config.roles.staff_role_id))
+    } else {
+        false
+    };
+
+    if !has_role {
+        ctx.send(poise::CreateReply::default()
+            .content("Vous n'avez pas la permission d'utiliser cette commande.")
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    }
+
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new("SELECT * FROM tickets WHERE 1 = 1");
+
+    if let Some(category) = &category {
+        query.push(" AND category = ").push_bind(format!("{:?}", category));
+    }
+    if let Some(tag) = &tag {
+        query.push(" AND channel_id IN (SELECT channel_id FROM ticket_tags WHERE tag = ").push_bind(normalize_tag(tag)).push(")");
+    }
+    if let Some(assignee) = &assignee {
+        query.push(" AND assigned_to = ").push_bind(assignee.id.get() as i64);
+    }
+    if let Some(hours) = min_age_hours {
+        query.push(" AND created_at <= ").push_bind(chrono::Utc::now().timestamp() - hours as i64 * 3600);
+    }
+    if let Some(waiting_on) = &waiting_on {
+        query.push(" AND awaiting_staff = ").push_bind(matches!(waiting_on, WaitingOn::Staff));
+    }
+    query.push(" ORDER BY created_at ASC");
+
+    let tickets: Vec<TicketInfo> = query.build_query_as().fetch_all(&data.db).await?;
+
+    if tickets.is_empty() {
+        ctx.send(poise::CreateReply::default()
+            .content("Aucun ticket ne correspond à ces filtres.")
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    }
+
+    let tags: Vec<(i64, String)> = sqlx::query_as("SELECT channel_id, tag FROM ticket_tags ORDER BY tag")
+        .fetch_all(&data.db)
+        .await?;
+
+    let guild_id = ctx.guild_id().unwrap();
+    let total = tickets.len();
+    let pages = tickets.chunks(TICKETS_PER_PAGE).enumerate().map(|(page_index, chunk)| {
+        let mut description = String::new();
+        let mut buttons = Vec::new();
+
+        for (i, ticket) in chunk.iter().enumerate() {
+            let position = page_index * TICKETS_PER_PAGE + i + 1;
+            let ticket_tags: Vec<String> = tags.iter()
+                .filter(|(channel_id, _)| *channel_id == ticket.channel_id)
+                .map(|(_, tag)| format!("`{}`", tag))
+                .collect();
+
+            let assigned = ticket.assigned_to.map(|id| format!("<@{}>", id)).unwrap_or("Personne".to_string());
+            let waiting = if ticket.awaiting_staff { "staff" } else { "utilisateur" };
+
+            description.push_str(&format!(
+                "**{}.** <#{}> — <@{}>\nCatégorie : {} • Assigné : {} • Ouvert <t:{}:R>\nEn attente : {}{}\n\n",
+                position,
+                ticket.channel_id,
+                ticket.user_id,
+                ticket.category,
+                assigned,
+                ticket.created_at,
+                waiting,
+                if ticket_tags.is_empty() { String::new() } else { format!(" • Tags : {}", ticket_tags.join(" ")) },
+            ));
+
+            buttons.push(serenity::CreateButton::new_link(format!("https://discord.com/channels/{}/{}", guild_id, ticket.channel_id))
+                .label(format!("#{}", position)));
+        }
+
+        Page {
+            embed: serenity::CreateEmbed::new()
+                .title(format!("Tickets ouverts ({})", total))
+                .description(description)
+                .color(0x3498db)
+                .footer(serenity::CreateEmbedFooter::new("VECTOR ©")),
+            buttons,
+        }
+    }).collect();
+
+    pagination::paginate(ctx, pages).await?;
+
+    Ok(())
+}
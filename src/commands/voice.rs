@@ -0,0 +1,244 @@
+use crate::{Context, Error};
+use poise::serenity_prelude as serenity;
+use crate::voice_system::{activity, panel, preferences, waiting};
+use crate::voice_system::activity::Period;
+use crate::commands::pagination::{self, Page};
+use tokio::sync::OwnedMutexGuard;
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("lock", "unlock", "limit", "rename", "permit", "reject", "claim", "transfer", "waitingroom", "reset", "stats", "leaderboard")
+)]
+pub async fn voice(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn reply(ctx: Context<'_>, content: impl Into<String>) -> Result<(), Error> {
+    ctx.send(poise::CreateReply::default().content(content).ephemeral(true)).await?;
+    Ok(())
+}
+
+async fn current_temp_channel(ctx: Context<'_>) -> Result<Option<(serenity::ChannelId, serenity::UserId, OwnedMutexGuard<()>)>, Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let Some(channel_id) = panel::member_voice_channel(ctx.serenity_context(), guild_id, ctx.author().id) else {
+        return Ok(None);
+    };
+
+    let guard = ctx.data().voice_locks.lock(channel_id.get()).await;
+    let owner = panel::channel_owner(&ctx.data().db, channel_id).await?;
+    Ok(owner.map(|owner_id| (channel_id, owner_id, guard)))
+}
+
+async fn owned_temp_channel(ctx: Context<'_>) -> Result<Option<(serenity::ChannelId, OwnedMutexGuard<()>)>, Error> {
+    match current_temp_channel(ctx).await? {
+        Some((channel_id, owner_id, guard)) if owner_id == ctx.author().id => Ok(Some((channel_id, guard))),
+        Some(_) => {
+            reply(ctx, "Seul le propriétaire du salon peut utiliser cette commande.").await?;
+            Ok(None)
+        }
+        None => {
+            reply(ctx, "Vous devez être dans un salon vocal temporaire.").await?;
+            Ok(None)
+        }
+    }
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn lock(ctx: Context<'_>) -> Result<(), Error> {
+    let Some((channel_id, _guard)) = owned_temp_channel(ctx).await? else { return Ok(()) };
+
+    panel::set_locked(ctx.serenity_context(), channel_id, true).await?;
+    preferences::save_locked(&ctx.data().db, ctx.author().id, true).await?;
+    reply(ctx, "🔒 Salon verrouillé.").await
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn unlock(ctx: Context<'_>) -> Result<(), Error> {
+    let Some((channel_id, _guard)) = owned_temp_channel(ctx).await? else { return Ok(()) };
+
+    panel::set_locked(ctx.serenity_context(), channel_id, false).await?;
+    preferences::save_locked(&ctx.data().db, ctx.author().id, false).await?;
+    reply(ctx, "🔓 Salon déverrouillé.").await
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn limit(
+    ctx: Context<'_>,
+    #[description = "Nombre maximum de membres (0 = illimité)"]
+    #[min = 0]
+    #[max = 99]
+    limit: u32,
+) -> Result<(), Error> {
+    let Some((channel_id, _guard)) = owned_temp_channel(ctx).await? else { return Ok(()) };
+
+    panel::set_user_limit(ctx.serenity_context(), channel_id, limit).await?;
+    preferences::save_user_limit(&ctx.data().db, ctx.author().id, limit).await?;
+    if limit == 0 {
+        reply(ctx, "Limite retirée.").await
+    } else {
+        reply(ctx, format!("Limite fixée à {} membres.", limit)).await
+    }
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn rename(
+    ctx: Context<'_>,
+    #[description = "Nouveau nom du salon"]
+    #[max_length = 100]
+    name: String,
+) -> Result<(), Error> {
+    let Some((channel_id, _guard)) = owned_temp_channel(ctx).await? else { return Ok(()) };
+
+    panel::rename(ctx.serenity_context(), channel_id, &name).await?;
+    preferences::save_name(&ctx.data().db, ctx.author().id, &name).await?;
+    reply(ctx, format!("Salon renommé en **{}**.", name)).await
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn permit(
+    ctx: Context<'_>,
+    #[description = "Le membre à autoriser"] user: serenity::User,
+) -> Result<(), Error> {
+    let Some((channel_id, _guard)) = owned_temp_channel(ctx).await? else { return Ok(()) };
+
+    if user.id == ctx.author().id || user.id == ctx.framework().bot_id {
+        return reply(ctx, "Vous ne pouvez pas autoriser ce membre.").await;
+    }
+
+    panel::permit_member(ctx.serenity_context(), channel_id, user.id).await?;
+    preferences::save_member(&ctx.data().db, ctx.author().id, user.id, true).await?;
+    waiting::forget_temporary_permit(&ctx.data().db, channel_id, user.id).await?;
+    reply(ctx, format!("<@{}> peut désormais rejoindre votre salon.", user.id)).await
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn reject(
+    ctx: Context<'_>,
+    #[description = "Le membre à refuser"] user: serenity::User,
+) -> Result<(), Error> {
+    let Some((channel_id, _guard)) = owned_temp_channel(ctx).await? else { return Ok(()) };
+
+    if user.id == ctx.author().id || user.id == ctx.framework().bot_id {
+        return reply(ctx, "Vous ne pouvez pas refuser ce membre.").await;
+    }
+
+    panel::reject_member(ctx.serenity_context(), ctx.guild_id().unwrap(), channel_id, user.id).await?;
+    preferences::save_member(&ctx.data().db, ctx.author().id, user.id, false).await?;
+    reply(ctx, format!("<@{}> ne peut plus rejoindre votre salon.", user.id)).await
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn claim(ctx: Context<'_>) -> Result<(), Error> {
+    let Some((channel_id, owner_id, _guard)) = current_temp_channel(ctx).await? else {
+        return reply(ctx, "Vous devez être dans un salon vocal temporaire.").await;
+    };
+
+    if owner_id == ctx.author().id {
+        return reply(ctx, "Vous êtes déjà le propriétaire de ce salon.").await;
+    }
+
+    let guild_id = ctx.guild_id().unwrap();
+    if panel::member_voice_channel(ctx.serenity_context(), guild_id, owner_id) == Some(channel_id) {
+        return reply(ctx, format!("Le propriétaire <@{}> est toujours présent dans le salon.", owner_id)).await;
+    }
+
+    crate::voice_system::events::transfer_ownership(ctx.serenity_context(), channel_id, ctx.author().id, ctx.data()).await?;
+    reply(ctx, "👑 Vous êtes maintenant le propriétaire de ce salon.").await
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn transfer(
+    ctx: Context<'_>,
+    #[description = "Le nouveau propriétaire"] user: serenity::User,
+) -> Result<(), Error> {
+    let Some((channel_id, _guard)) = owned_temp_channel(ctx).await? else { return Ok(()) };
+
+    let guild_id = ctx.guild_id().unwrap();
+    if user.bot || user.id == ctx.author().id || panel::member_voice_channel(ctx.serenity_context(), guild_id, user.id) != Some(channel_id) {
+        return reply(ctx, "Le nouveau propriétaire doit être un membre présent dans votre salon.").await;
+    }
+
+    crate::voice_system::events::transfer_ownership(ctx.serenity_context(), channel_id, user.id, ctx.data()).await?;
+    reply(ctx, format!("La propriété du salon a été transférée à <@{}>.", user.id)).await
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn waitingroom(
+    ctx: Context<'_>,
+    #[description = "Activer ou désactiver la salle d'attente"] enabled: bool,
+) -> Result<(), Error> {
+    let Some((channel_id, _guard)) = owned_temp_channel(ctx).await? else { return Ok(()) };
+
+    if enabled {
+        let waiting_id = waiting::enable(ctx.serenity_context(), channel_id, ctx.data()).await?;
+        preferences::save_locked(&ctx.data().db, ctx.author().id, true).await?;
+        reply(ctx, format!("⏳ Salle d'attente activée : <#{}>. Votre salon est verrouillé.", waiting_id)).await
+    } else if waiting::disable(ctx.serenity_context(), channel_id, ctx.data()).await? {
+        reply(ctx, "⏳ Salle d'attente désactivée.").await
+    } else {
+        reply(ctx, "Votre salon n'a pas de salle d'attente.").await
+    }
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn reset(ctx: Context<'_>) -> Result<(), Error> {
+    preferences::reset(&ctx.data().db, ctx.author().id).await?;
+    reply(ctx, "Vos préférences de salon vocal ont été réinitialisées.").await
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn stats(
+    ctx: Context<'_>,
+    #[description = "Le membre à consulter (par défaut : vous)"] user: Option<serenity::User>,
+) -> Result<(), Error> {
+    let target = user.as_ref().unwrap_or(ctx.author());
+    let db = &ctx.data().db;
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(format!("Activité vocale de {}", target.name))
+        .thumbnail(target.face())
+        .color(0x3498db)
+        .footer(serenity::CreateEmbedFooter::new("Hors salon AFK et temps en sourdine"))
+        .timestamp(serenity::Timestamp::now());
+
+    for period in [Period::Day, Period::Week, Period::Month, Period::All] {
+        let total = activity::user_total(db, target.id, period).await?;
+        embed = embed.field(poise::ChoiceParameter::name(&period), activity::format_duration(total), true);
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true)).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only)]
+pub async fn leaderboard(
+    ctx: Context<'_>,
+    #[description = "Période du classement"] period: Option<Period>,
+) -> Result<(), Error> {
+    let period = period.unwrap_or(Period::Week);
+    let entries = activity::leaderboard(&ctx.data().db, period, 50).await?;
+
+    if entries.is_empty() {
+        return reply(ctx, "Aucune activité vocale enregistrée sur cette période.").await;
+    }
+
+    let pages = entries.chunks(10).enumerate().map(|(page_index, chunk)| {
+        let description = chunk.iter().enumerate()
+            .map(|(i, (user_id, total))| format!("**{}.** <@{}> — {}", page_index * 10 + i + 1, user_id, activity::format_duration(*total)))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Page {
+            embed: serenity::CreateEmbed::new()
+                .title(format!("Classement vocal — {}", poise::ChoiceParameter::name(&period)))
+                .description(description)
+                .color(0xf1c40f)
+                .footer(serenity::CreateEmbedFooter::new("Hors salon AFK et temps en sourdine")),
+            buttons: Vec::new(),
+        }
+    }).collect();
+
+    pagination::paginate(ctx, pages).await
+}
@@ -6,6 +6,14 @@ pub struct Config {
     pub roles: Roles,
     pub channels: Channels,
     pub categories: Categories,
+    #[serde(default)]
+    pub tickets: TicketSettings,
+    #[serde(default)]
+    pub voice_hubs: Vec<VoiceHub>,
+    #[serde(default)]
+    pub voice: VoiceSettings,
+    #[serde(default)]
+    pub giveaways: GiveawaySettings,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -17,9 +25,10 @@ pub struct Roles {
 #[derive(Debug, Deserialize, Clone)]
 pub struct Channels {
     pub log_channel_id: u64,
-    pub jtc_channel_ids: Vec<u64>,
     pub voice_log_channel_id: u64,
     pub giveaway_log_channel_id: u64,
+    #[serde(default)]
+    pub jtc_channel_ids: Vec<u64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -31,10 +40,150 @@ pub struct Categories {
     pub voice_category_id: u64,
 }
 
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TicketMode {
+    #[default]
+    Channel,
+    Thread,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TicketSettings {
+    #[serde(default)]
+    pub mode: TicketMode,
+    #[serde(default)]
+    pub thread_parent_id: u64,
+    #[serde(default)]
+    pub predefined_tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum VoicePreset {
+    #[default]
+    Public,
+    Locked,
+    Hidden,
+    Staff,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum VoiceRoomKind {
+    #[default]
+    Voice,
+    Stage,
+}
+
+fn default_name_template() -> String {
+    "🔉〢{name}".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VoiceHub {
+    pub channel_id: u64,
+    #[serde(default)]
+    pub category_id: Option<u64>,
+    #[serde(default = "default_name_template")]
+    pub name_template: String,
+    #[serde(default)]
+    pub user_limit: Option<u32>,
+    #[serde(default)]
+    pub bitrate: Option<u32>,
+    #[serde(default)]
+    pub preset: VoicePreset,
+    #[serde(default = "default_true")]
+    pub allow_preferences: bool,
+    #[serde(default)]
+    pub kind: VoiceRoomKind,
+    #[serde(default)]
+    pub welcome_message: Option<String>,
+    #[serde(default)]
+    pub export_chat: bool,
+}
+
+impl VoiceHub {
+    fn legacy(channel_id: u64) -> Self {
+        Self {
+            channel_id,
+            category_id: None,
+            name_template: default_name_template(),
+            user_limit: None,
+            bitrate: None,
+            preset: VoicePreset::default(),
+            allow_preferences: true,
+            kind: VoiceRoomKind::default(),
+            welcome_message: None,
+            export_chat: false,
+        }
+    }
+}
+
+fn default_reclaim_grace_secs() -> i64 {
+    120
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VoiceSettings {
+    #[serde(default)]
+    pub successor_role_priority: Vec<u64>,
+    #[serde(default = "default_reclaim_grace_secs")]
+    pub reclaim_grace_secs: i64,
+}
+
+impl Default for VoiceSettings {
+    fn default() -> Self {
+        Self {
+            successor_role_priority: Vec::new(),
+            reclaim_grace_secs: default_reclaim_grace_secs(),
+        }
+    }
+}
+
 impl Config {
     pub fn load() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         let content = fs::read_to_string("config.toml")?;
-        let config: Config = toml::from_str(&content)?;
+        let mut config: Config = toml::from_str(&content)?;
+
+        if !config.channels.jtc_channel_ids.is_empty() {
+            if config.voice_hubs.is_empty() {
+                eprintln!("[channels] jtc_channel_ids est obsolète : utilisez des sections [[voice_hubs]]. Les salons listés sont utilisés comme hubs par défaut.");
+                config.voice_hubs = config.channels.jtc_channel_ids.iter().map(|&id| VoiceHub::legacy(id)).collect();
+            } else {
+                eprintln!("[channels] jtc_channel_ids est ignoré car des [[voice_hubs]] sont configurés.");
+            }
+        }
+
+        if config.tickets.mode == TicketMode::Thread && config.tickets.thread_parent_id == 0 {
+            return Err("[tickets] mode = \"thread\" nécessite un thread_parent_id".into());
+        }
+
         Ok(config)
     }
 }
+
+fn default_claim_window_secs() -> i64 {
+    86400
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GiveawaySettings {
+    #[serde(default = "default_claim_window_secs")]
+    pub claim_window_secs: i64,
+    #[serde(default)]
+    pub open_ticket_on_claim: bool,
+}
+
+impl Default for GiveawaySettings {
+    fn default() -> Self {
+        Self {
+            claim_window_secs: default_claim_window_secs(),
+            open_ticket_on_claim: false,
+        }
+    }
+}
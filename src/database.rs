@@ -24,6 +24,37 @@ pub async fn init_db() -> Result<Pool<Sqlite>, sqlx::Error> {
         )"
     ).execute(&pool).await?;
 
+    let _ = sqlx::query("ALTER TABLE tickets ADD COLUMN assigned_to INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE tickets ADD COLUMN awaiting_staff BOOLEAN NOT NULL DEFAULT 1").execute(&pool).await;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS ticket_tags (
+            channel_id INTEGER NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (channel_id, tag)
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS ticket_reminders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            channel_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+            staff_id INTEGER NOT NULL,
+            message TEXT NOT NULL,
+            due_at INTEGER NOT NULL
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS ticket_receipts (
+            message_id INTEGER PRIMARY KEY,
+            user_id INTEGER NOT NULL,
+            dm_channel_id INTEGER NOT NULL
+        )"
+    ).execute(&pool).await?;
+
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS blacklist (
             user_id INTEGER PRIMARY KEY,
@@ -47,6 +78,65 @@ pub async fn init_db() -> Result<Pool<Sqlite>, sqlx::Error> {
         )"
     ).execute(&pool).await?;
 
+    let _ = sqlx::query("ALTER TABLE voice_channels ADD COLUMN hub_id INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE voice_channels ADD COLUMN number INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE voice_channels ADD COLUMN waiting_channel_id INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE voice_channels ADD COLUMN previous_owner_id INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE voice_channels ADD COLUMN owner_left_at INTEGER").execute(&pool).await;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS voice_presence (
+            channel_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+            joined_at INTEGER NOT NULL,
+            PRIMARY KEY (channel_id, user_id)
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS voice_temp_permits (
+            channel_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+            PRIMARY KEY (channel_id, user_id)
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS voice_preferences (
+            user_id INTEGER PRIMARY KEY,
+            name_template TEXT,
+            user_limit INTEGER,
+            bitrate INTEGER,
+            locked BOOLEAN NOT NULL DEFAULT 0
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS voice_preference_members (
+            owner_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+            allowed BOOLEAN NOT NULL,
+            PRIMARY KEY (owner_id, user_id)
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS voice_sessions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            channel_id INTEGER NOT NULL,
+            muted BOOLEAN NOT NULL,
+            deafened BOOLEAN NOT NULL,
+            counted BOOLEAN NOT NULL,
+            started_at INTEGER NOT NULL,
+            ended_at INTEGER,
+            last_seen INTEGER NOT NULL
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_voice_sessions_user ON voice_sessions (user_id, ended_at)")
+        .execute(&pool).await?;
+
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS giveaways (
             message_id INTEGER PRIMARY KEY,
@@ -61,6 +151,12 @@ pub async fn init_db() -> Result<Pool<Sqlite>, sqlx::Error> {
         )"
     ).execute(&pool).await?;
 
+    let _ = sqlx::query("ALTER TABLE giveaways ADD COLUMN min_member_days INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE giveaways ADD COLUMN min_account_days INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE giveaways ADD COLUMN min_voice_minutes INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE giveaways ADD COLUMN min_messages INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE giveaways ADD COLUMN announced BOOLEAN NOT NULL DEFAULT 1").execute(&pool).await;
+
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS giveaway_participants (
             giveaway_message_id INTEGER NOT NULL,
@@ -69,5 +165,61 @@ pub async fn init_db() -> Result<Pool<Sqlite>, sqlx::Error> {
         )"
     ).execute(&pool).await?;
 
+    let _ = sqlx::query("ALTER TABLE giveaway_participants ADD COLUMN weight INTEGER NOT NULL DEFAULT 1").execute(&pool).await;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS giveaway_roles (
+            giveaway_message_id INTEGER NOT NULL,
+            role_id INTEGER NOT NULL,
+            required BOOLEAN NOT NULL,
+            PRIMARY KEY (giveaway_message_id, role_id)
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS giveaway_bonus_roles (
+            giveaway_message_id INTEGER NOT NULL,
+            role_id INTEGER NOT NULL,
+            entries INTEGER NOT NULL,
+            PRIMARY KEY (giveaway_message_id, role_id)
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS giveaway_winners (
+            giveaway_message_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+            won_at INTEGER NOT NULL,
+            PRIMARY KEY (giveaway_message_id, user_id)
+        )"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS scheduled_giveaways (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            channel_id INTEGER NOT NULL,
+            host_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL,
+            reward TEXT NOT NULL,
+            winner_count INTEGER NOT NULL,
+            duration_secs INTEGER NOT NULL,
+            ping_role_id INTEGER,
+            start_at INTEGER NOT NULL
+        )"
+    ).execute(&pool).await?;
+
+    let _ = sqlx::query("ALTER TABLE giveaway_winners ADD COLUMN claim_deadline INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE giveaway_winners ADD COLUMN claimed_at INTEGER").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE giveaway_winners ADD COLUMN forfeited BOOLEAN NOT NULL DEFAULT 0").execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE giveaway_winners ADD COLUMN replaced BOOLEAN NOT NULL DEFAULT 1").execute(&pool).await;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS message_counts (
+            user_id INTEGER PRIMARY KEY,
+            count INTEGER NOT NULL DEFAULT 0
+        )"
+    ).execute(&pool).await?;
+
     Ok(pool)
 }
@@ -0,0 +1,359 @@
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DurationError {
+    Empty,
+    UnexpectedText(String),
+    MissingUnit(String),
+    UnknownUnit(String),
+    Zero,
+    InvalidDate(String),
+    InPast,
+    TooShort(i64),
+    TooLong(i64),
+    Overflow,
+}
+
+impl std::fmt::Display for DurationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DurationError::Empty => write!(f, "Aucune durée indiquée."),
+            DurationError::UnexpectedText(text) => write!(f, "« {} » n'est pas une durée valide. Exemples : 1j2h30m, 2sem, 45m, 25/12/2026 18:00.", text),
+            DurationError::MissingUnit(number) => write!(f, "« {} » doit être suivi d'une unité (s, m, h, j, sem).", number),
+            DurationError::UnknownUnit(unit) => write!(f, "Unité « {} » inconnue. Unités acceptées : s, m, h, j (ou d), sem (ou w).", unit),
+            DurationError::Zero => write!(f, "La durée doit être supérieure à zéro."),
+            DurationError::InvalidDate(text) => write!(f, "Date « {} » invalide. Formats acceptés : JJ/MM/AAAA HH:MM, AAAA-MM-JJ HH:MM ou HH:MM, suivis d'un fuseau optionnel (UTC par défaut, ou +02:00 par exemple).", text),
+            DurationError::InPast => write!(f, "Cette date est déjà passée."),
+            DurationError::TooShort(min) => write!(f, "La durée minimale est de {}.", describe(*min)),
+            DurationError::TooLong(max) => write!(f, "La durée maximale est de {}.", describe(*max)),
+            DurationError::Overflow => write!(f, "Cette durée est beaucoup trop longue."),
+        }
+    }
+}
+
+impl std::error::Error for DurationError {}
+
+pub fn describe(seconds: i64) -> String {
+    let parts = [(seconds / 86400, "j"), (seconds % 86400 / 3600, "h"), (seconds % 3600 / 60, "m"), (seconds % 60, "s")];
+
+    let text: Vec<String> = parts.iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+
+    if text.is_empty() { "0s".to_string() } else { text.join(" ") }
+}
+
+fn unit_seconds(unit: &str) -> Option<i64> {
+    match unit {
+        "s" | "sec" | "secs" | "seconde" | "secondes" | "second" | "seconds" => Some(1),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(60),
+        "h" | "hr" | "hrs" | "heure" | "heures" | "hour" | "hours" => Some(3600),
+        "j" | "jour" | "jours" | "d" | "day" | "days" => Some(86400),
+        "w" | "sem" | "semaine" | "semaines" | "week" | "weeks" => Some(7 * 86400),
+        _ => None,
+    }
+}
+
+pub fn parse_duration(input: &str) -> Result<i64, DurationError> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return Err(DurationError::Empty);
+    }
+
+    let mut chars = input.chars().peekable();
+    let mut total: i64 = 0;
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',' || *c == '+').is_some() {}
+
+        let Some(&next) = chars.peek() else { break };
+        if !next.is_ascii_digit() {
+            let rest: String = chars.take_while(|c| !c.is_whitespace() && *c != ',').collect();
+            return Err(DurationError::UnexpectedText(rest));
+        }
+
+        let mut number = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+            number.push(c);
+        }
+
+        while chars.next_if(|c| *c == ' ').is_some() {}
+
+        let mut unit = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_alphabetic()) {
+            unit.push(c);
+        }
+
+        if unit.is_empty() {
+            return Err(DurationError::MissingUnit(number));
+        }
+
+        let factor = unit_seconds(&unit).ok_or(DurationError::UnknownUnit(unit))?;
+        let value: i64 = number.parse().map_err(|_| DurationError::Overflow)?;
+
+        total = value.checked_mul(factor)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or(DurationError::Overflow)?;
+    }
+
+    if total == 0 {
+        return Err(DurationError::Zero);
+    }
+
+    Ok(total)
+}
+
+fn parse_offset(token: &str) -> Option<FixedOffset> {
+    if matches!(token, "utc" | "gmt" | "z") {
+        return FixedOffset::east_opt(0);
+    }
+
+    let token = token.strip_prefix("utc").or_else(|| token.strip_prefix("gmt")).unwrap_or(token);
+    let sign = match token.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+
+    let digits: String = token[1..].chars().filter(|c| *c != ':').collect();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let (hours, minutes): (i32, i32) = match digits.len() {
+        1 | 2 => (digits.parse().ok()?, 0),
+        3 | 4 => {
+            let split = digits.len() - 2;
+            (digits[..split].parse().ok()?, digits[split..].parse().ok()?)
+        }
+        _ => return None,
+    };
+
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn looks_like_date(input: &str) -> bool {
+    input.contains('/') || input.contains(':') || (input.contains('-') && input.chars().take(4).all(|c| c.is_ascii_digit()))
+}
+
+fn resolve<Tz: TimeZone>(tz: &Tz, text: &str, now: i64) -> Option<i64> {
+    const DATETIME_FORMATS: [&str; 4] = ["%d/%m/%Y %H:%M", "%d/%m/%Y %Hh%M", "%Y-%m-%d %H:%M", "%Y-%m-%dt%H:%M"];
+
+    for format in DATETIME_FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(text, format) {
+            return tz.from_local_datetime(&naive).single().map(|d| d.timestamp());
+        }
+    }
+
+    for format in ["%d/%m/%Y", "%Y-%m-%d"] {
+        if let Ok(date) = NaiveDate::parse_from_str(text, format) {
+            return tz.from_local_datetime(&date.and_time(NaiveTime::MIN)).single().map(|d| d.timestamp());
+        }
+    }
+
+    let time = NaiveTime::parse_from_str(text, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(text, "%Hh%M"))
+        .ok()?;
+    let today = tz.timestamp_opt(now, 0).single()?.date_naive();
+    let candidate = tz.from_local_datetime(&today.and_time(time)).single()?.timestamp();
+
+    Some(if candidate <= now { candidate + 86400 } else { candidate })
+}
+
+pub fn parse_datetime(input: &str, now: i64) -> Result<Option<i64>, DurationError> {
+    let input = input.trim();
+
+    if let Ok(date) = chrono::DateTime::parse_from_rfc3339(input) {
+        return Ok(Some(date.timestamp()));
+    }
+
+    let lowered = input.to_lowercase();
+    if !looks_like_date(&lowered) {
+        return Ok(None);
+    }
+
+    let (text, offset) = match lowered.rsplit_once(' ') {
+        Some((text, zone)) if parse_offset(zone).is_some() => (text.trim(), parse_offset(zone)),
+        _ => (lowered.as_str(), None),
+    };
+
+    let timestamp = match offset {
+        Some(offset) => resolve(&offset, text, now),
+        None => resolve(&chrono::Utc, text, now),
+    };
+
+    timestamp.map(Some).ok_or_else(|| DurationError::InvalidDate(input.to_string()))
+}
+
+pub fn parse_deadline(input: &str, now: i64) -> Result<i64, DurationError> {
+    let deadline = match parse_datetime(input, now)? {
+        Some(timestamp) => timestamp,
+        None => now.checked_add(parse_duration(input)?).ok_or(DurationError::Overflow)?,
+    };
+
+    if deadline <= now {
+        return Err(DurationError::InPast);
+    }
+
+    Ok(deadline)
+}
+
+pub fn check_bounds(seconds: i64, min: i64, max: i64) -> Result<i64, DurationError> {
+    if seconds < min {
+        return Err(DurationError::TooShort(min));
+    }
+    if seconds > max {
+        return Err(DurationError::TooLong(max));
+    }
+    Ok(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_790_000_000;
+
+    #[test]
+    fn parses_single_units() {
+        assert_eq!(parse_duration("10s"), Ok(10));
+        assert_eq!(parse_duration("30m"), Ok(1800));
+        assert_eq!(parse_duration("2h"), Ok(7200));
+        assert_eq!(parse_duration("1j"), Ok(86400));
+        assert_eq!(parse_duration("1d"), Ok(86400));
+        assert_eq!(parse_duration("2w"), Ok(14 * 86400));
+        assert_eq!(parse_duration("1 sem"), Ok(7 * 86400));
+    }
+
+    #[test]
+    fn parses_compound_forms() {
+        assert_eq!(parse_duration("1d2h30m"), Ok(86400 + 7200 + 1800));
+        assert_eq!(parse_duration("1j 2h 30m"), Ok(86400 + 7200 + 1800));
+        assert_eq!(parse_duration("1h,30m"), Ok(5400));
+        assert_eq!(parse_duration("1w1d"), Ok(8 * 86400));
+        assert_eq!(parse_duration("2 heures 15 minutes"), Ok(8100));
+    }
+
+    #[test]
+    fn is_case_insensitive() {
+        assert_eq!(parse_duration("1H30M"), Ok(5400));
+        assert_eq!(parse_duration(" 3 Jours "), Ok(3 * 86400));
+    }
+
+    #[test]
+    fn rejects_unknown_units() {
+        assert_eq!(parse_duration("5x,1h"), Err(DurationError::UnknownUnit("x".to_string())));
+        assert_eq!(parse_duration("3mo"), Err(DurationError::UnknownUnit("mo".to_string())));
+    }
+
+    #[test]
+    fn rejects_missing_units_and_garbage() {
+        assert_eq!(parse_duration("30"), Err(DurationError::MissingUnit("30".to_string())));
+        assert_eq!(parse_duration("1,5h"), Err(DurationError::MissingUnit("1".to_string())));
+        assert_eq!(parse_duration("1.5h"), Err(DurationError::MissingUnit("1".to_string())));
+        assert_eq!(parse_duration("abc"), Err(DurationError::UnexpectedText("abc".to_string())));
+        assert_eq!(parse_duration("1h -2m"), Err(DurationError::UnexpectedText("-2m".to_string())));
+    }
+
+    #[test]
+    fn rejects_empty_and_zero() {
+        assert_eq!(parse_duration(""), Err(DurationError::Empty));
+        assert_eq!(parse_duration("   "), Err(DurationError::Empty));
+        assert_eq!(parse_duration("0m"), Err(DurationError::Zero));
+    }
+
+    #[test]
+    fn rejects_overflow() {
+        assert_eq!(parse_duration("99999999999999999999s"), Err(DurationError::Overflow));
+        assert_eq!(parse_duration("9999999999999999w"), Err(DurationError::Overflow));
+    }
+
+    #[test]
+    fn parses_offsets() {
+        assert_eq!(parse_offset("utc"), FixedOffset::east_opt(0));
+        assert_eq!(parse_offset("+02:00"), FixedOffset::east_opt(7200));
+        assert_eq!(parse_offset("+2"), FixedOffset::east_opt(7200));
+        assert_eq!(parse_offset("-0530"), FixedOffset::east_opt(-(5 * 3600 + 30 * 60)));
+        assert_eq!(parse_offset("utc+1"), FixedOffset::east_opt(3600));
+        assert_eq!(parse_offset("+15"), None);
+        assert_eq!(parse_offset("paris"), None);
+    }
+
+    #[test]
+    fn parses_absolute_dates_with_timezone() {
+        let expected = chrono::DateTime::parse_from_rfc3339("2026-12-25T18:00:00+00:00").unwrap().timestamp();
+
+        assert_eq!(parse_datetime("25/12/2026 18:00 UTC", NOW), Ok(Some(expected)));
+        assert_eq!(parse_datetime("2026-12-25 20:00 +02:00", NOW), Ok(Some(expected)));
+        assert_eq!(parse_datetime("25/12/2026 18h00 utc", NOW), Ok(Some(expected)));
+        assert_eq!(parse_datetime("2026-12-25T19:00:00+01:00", NOW), Ok(Some(expected)));
+    }
+
+    #[test]
+    fn dates_without_timezone_are_utc() {
+        let expected = chrono::DateTime::parse_from_rfc3339("2026-12-25T18:00:00+00:00").unwrap().timestamp();
+
+        assert_eq!(parse_datetime("25/12/2026 18:00", NOW), Ok(Some(expected)));
+        assert_eq!(parse_datetime("2026-12-25 18:00", NOW), Ok(Some(expected)));
+    }
+
+    #[test]
+    fn parses_date_only_as_midnight() {
+        let expected = chrono::DateTime::parse_from_rfc3339("2026-12-25T00:00:00+00:00").unwrap().timestamp();
+        assert_eq!(parse_datetime("25/12/2026 UTC", NOW), Ok(Some(expected)));
+    }
+
+    #[test]
+    fn time_only_means_next_occurrence() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-10-19T20:00:00+00:00").unwrap().timestamp();
+        let later_today = chrono::DateTime::parse_from_rfc3339("2026-10-19T21:30:00+00:00").unwrap().timestamp();
+        let tomorrow = chrono::DateTime::parse_from_rfc3339("2026-10-20T08:00:00+00:00").unwrap().timestamp();
+
+        assert_eq!(parse_datetime("21:30 UTC", now), Ok(Some(later_today)));
+        assert_eq!(parse_datetime("08:00 UTC", now), Ok(Some(tomorrow)));
+    }
+
+    #[test]
+    fn durations_are_not_dates() {
+        assert_eq!(parse_datetime("1d2h", NOW), Ok(None));
+        assert_eq!(parse_datetime("45m", NOW), Ok(None));
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        assert_eq!(parse_datetime("32/13/2026 18:00", NOW), Err(DurationError::InvalidDate("32/13/2026 18:00".to_string())));
+        assert_eq!(parse_datetime("25/12/2026 25:00 UTC", NOW), Err(DurationError::InvalidDate("25/12/2026 25:00 UTC".to_string())));
+    }
+
+    #[test]
+    fn deadline_accepts_durations_and_dates() {
+        assert_eq!(parse_deadline("1h30m", NOW), Ok(NOW + 5400));
+
+        let date = chrono::DateTime::parse_from_rfc3339("2026-12-25T18:00:00+00:00").unwrap().timestamp();
+        assert_eq!(parse_deadline("25/12/2026 18:00 UTC", NOW), Ok(date));
+    }
+
+    #[test]
+    fn deadline_rejects_past_dates() {
+        assert_eq!(parse_deadline("01/01/2020 12:00 UTC", NOW), Err(DurationError::InPast));
+    }
+
+    #[test]
+    fn enforces_bounds() {
+        assert_eq!(check_bounds(60, 10, 3600), Ok(60));
+        assert_eq!(check_bounds(5, 10, 3600), Err(DurationError::TooShort(10)));
+        assert_eq!(check_bounds(7200, 10, 3600), Err(DurationError::TooLong(3600)));
+    }
+
+    #[test]
+    fn describes_durations() {
+        assert_eq!(describe(10), "10s");
+        assert_eq!(describe(90 * 86400), "90j");
+        assert_eq!(describe(86400 + 5400), "1j 1h 30m");
+    }
+}
@@ -1,6 +1,32 @@
 use poise::serenity_prelude as serenity;
 use crate::Data;
 use rand::prelude::IndexedRandom;
+use crate::duration::{check_bounds, parse_deadline, DurationError};
+use crate::giveaway_system::{history, manage, requirements};
+use crate::giveaway_system::structs::NewGiveaway;
+use crate::ticket_system::structs::TicketCategory;
+
+pub const MIN_GIVEAWAY_SECS: i64 = 10;
+pub const MAX_GIVEAWAY_SECS: i64 = 90 * 86400;
+pub const MAX_WINNERS: i32 = 20;
+const ANNOUNCE_GRACE_SECS: i64 = 60;
+
+pub fn giveaway_end_time(input: &str, start: i64) -> Result<i64, DurationError> {
+    let end = parse_deadline(input, start)?;
+    check_bounds(end - start, MIN_GIVEAWAY_SECS, MAX_GIVEAWAY_SECS)?;
+    Ok(end)
+}
+
+pub fn parse_winner_count(input: &str) -> Result<i32, String> {
+    let count: i32 = input.trim().parse()
+        .map_err(|_| format!("« {} » n'est pas un nombre de gagnants valide.", input.trim()))?;
+
+    if !(1..=MAX_WINNERS).contains(&count) {
+        return Err(format!("Le nombre de gagnants doit être compris entre 1 et {}.", MAX_WINNERS));
+    }
+
+    Ok(count)
+}
 
 pub async fn handle_event(
     ctx: &serenity::Context,
@@ -8,16 +34,22 @@ pub async fn handle_event(
     _framework: poise::FrameworkContext<'_, Data, crate::Error>,
     data: &Data,
 ) -> Result<(), crate::Error> {
-    if let serenity::FullEvent::InteractionCreate { interaction } = event {
-        match interaction {
-            serenity::Interaction::Component(component) => {
-                handle_component(ctx, component, data).await?;
-            }
-            serenity::Interaction::Modal(modal) => {
-                handle_modal(ctx, modal, data).await?;
+    match event {
+        serenity::FullEvent::InteractionCreate { interaction } => {
+            match interaction {
+                serenity::Interaction::Component(component) => {
+                    handle_component(ctx, component, data).await?;
+                }
+                serenity::Interaction::Modal(modal) => {
+                    handle_modal(ctx, modal, data).await?;
+                }
+                _ => {}
             }
-            _ => {}
         }
+        serenity::FullEvent::Message { new_message } if new_message.guild_id.is_some() && !new_message.author.bot => {
+            requirements::record_message(&data.db, new_message.author.id).await;
+        }
+        _ => {}
     }
     Ok(())
 }
@@ -28,26 +60,13 @@ async fn handle_component(
     data: &Data,
 ) -> Result<(), crate::Error> {
     let custom_id = &component.data.custom_id;
-    let channel_id = component.channel_id;
     let user_id = component.user.id;
 
-    if custom_id == "giveaway_create_btn" {
-        let exists: bool = sqlx::query_scalar(
-            "SELECT EXISTS(SELECT 1 FROM giveaways WHERE channel_id = ? AND status = 'active')"
-        )
-        .bind(channel_id.get() as i64)
-        .fetch_one(&data.db)
-        .await?;
-
-        if exists {
-            component.create_response(ctx, serenity::CreateInteractionResponse::Message(
-                serenity::CreateInteractionResponseMessage::new()
-                    .content("Un giveaway est déjà en cours dans ce salon.")
-                    .ephemeral(true)
-            )).await?;
-            return Ok(());
-        }
-
+    if custom_id.starts_with("giveaway_manage") {
+        manage::handle_component(ctx, component, data).await?;
+    } else if custom_id.starts_with("giveaway_history_page:") {
+        history::handle_component(ctx, component, data).await?;
+    } else if custom_id == "giveaway_create_btn" {
         let modal = serenity::CreateModal::new("giveaway_create_modal", "Créer un Giveaway")
             .components(vec![
                 serenity::CreateActionRow::InputText(serenity::CreateInputText::new(
@@ -57,8 +76,8 @@ async fn handle_component(
                     serenity::InputTextStyle::Paragraph, "Description", "description"
                 )),
                 serenity::CreateActionRow::InputText(serenity::CreateInputText::new(
-                    serenity::InputTextStyle::Short, "Durée (ex: 1j, 1h, 30m)", "duration"
-                ).placeholder("1j ou 1h ou 30m")),
+                    serenity::InputTextStyle::Short, "Durée ou date de fin", "duration"
+                ).placeholder("1j2h30m, 2sem ou 25/12/2026 18:00 (heure UTC par défaut)")),
                 serenity::CreateActionRow::InputText(serenity::CreateInputText::new(
                     serenity::InputTextStyle::Short, "Récompense", "reward"
                 )),
@@ -68,51 +87,8 @@ async fn handle_component(
             ]);
 
         component.create_response(ctx, serenity::CreateInteractionResponse::Modal(modal)).await?;
-    } else if custom_id == "giveaway_delete_btn" {
-        let giveaway: Option<(i64, i64, String)> = sqlx::query_as(
-            "SELECT message_id, host_id, title FROM giveaways WHERE channel_id = ? AND status = 'active'"
-        )
-        .bind(channel_id.get() as i64)
-        .fetch_optional(&data.db)
-        .await?;
-
-        if let Some((message_id, host_id, title)) = giveaway {
-            sqlx::query("DELETE FROM giveaways WHERE message_id = ?")
-                .bind(message_id)
-                .execute(&data.db)
-                .await?;
-
-            sqlx::query("DELETE FROM giveaway_participants WHERE giveaway_message_id = ?")
-                .bind(message_id)
-                .execute(&data.db)
-                .await?;
-
-            let _ = channel_id.delete_message(ctx, serenity::MessageId::new(message_id as u64)).await;
-
-            component.create_response(ctx, serenity::CreateInteractionResponse::Message(
-                serenity::CreateInteractionResponseMessage::new()
-                    .content("Giveaway supprimé avec succès.")
-                    .ephemeral(true)
-            )).await?;
-
-            let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
-            let embed = serenity::CreateEmbed::new()
-                .title("Giveaway Supprimé")
-                .field("Titre", title, false)
-                .field("Supprimé par", format!("<@{}>", user_id), true)
-                .field("Créé par", format!("<@{}>", host_id), true)
-                .color(0xe74c3c)
-                .timestamp(serenity::Timestamp::now());
-            
-            let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(embed)).await;
-
-        } else {
-            component.create_response(ctx, serenity::CreateInteractionResponse::Message(
-                serenity::CreateInteractionResponseMessage::new()
-                    .content("Aucun giveaway actif dans ce salon.")
-                    .ephemeral(true)
-            )).await?;
-        }
+    } else if let Some(message_id) = custom_id.strip_prefix("giveaway_claim:") {
+        claim_prize(ctx, component, data, message_id.parse().unwrap_or_default()).await?;
     } else if custom_id == "giveaway_join" {
         let message_id = component.message.id.get() as i64;
         
@@ -155,15 +131,42 @@ async fn handle_component(
                     .ephemeral(true)
             )).await?;
         } else {
-            sqlx::query("INSERT INTO giveaway_participants (giveaway_message_id, user_id) VALUES (?, ?)")
+            let Some(member) = &component.member else {
+                component.create_response(ctx, serenity::CreateInteractionResponse::Message(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .content("❌ Impossible de vérifier votre profil sur ce serveur.")
+                        .ephemeral(true)
+                )).await?;
+                return Ok(());
+            };
+
+            if let Some(reason) = requirements::check(&data.db, message_id, member).await? {
+                component.create_response(ctx, serenity::CreateInteractionResponse::Message(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .content(format!("❌ {}", reason))
+                        .ephemeral(true)
+                )).await?;
+                return Ok(());
+            }
+
+            let weight = requirements::entry_weight(&data.db, message_id, member).await?;
+
+            sqlx::query("INSERT INTO giveaway_participants (giveaway_message_id, user_id, weight) VALUES (?, ?, ?)")
                 .bind(message_id)
                 .bind(user_id_i64)
+                .bind(weight)
                 .execute(&data.db)
                 .await?;
+
+            let content = if weight > 1 {
+                format!("Participation enregistrée ! Grâce à vos rôles, vous disposez de **{}** entrées.", weight)
+            } else {
+                "Participation enregistrée ! Vous disposez de **1** entrée.".to_string()
+            };
             
             component.create_response(ctx, serenity::CreateInteractionResponse::Message(
                 serenity::CreateInteractionResponseMessage::new()
-                    .content("Participation enregistrée !")
+                    .content(content)
                     .ephemeral(true)
             )).await?;
         }
@@ -192,6 +195,10 @@ async fn handle_modal(
     modal: &serenity::ModalInteraction,
     data: &Data,
 ) -> Result<(), crate::Error> {
+    if modal.data.custom_id.starts_with("giveaway_edit_modal:") {
+        return manage::handle_edit_modal(ctx, modal, data).await;
+    }
+
     if modal.data.custom_id != "giveaway_create_modal" {
         return Ok(());
     }
@@ -215,190 +222,907 @@ async fn handle_modal(
         }
     }
 
-    let duration_secs = match parse_duration(&duration_str) {
-        Some(s) => s,
-        None => {
+    let now = chrono::Utc::now().timestamp();
+    let parsed = giveaway_end_time(&duration_str, now)
+        .map_err(|why| why.to_string())
+        .and_then(|end_time| parse_winner_count(&winners_str).map(|count| (end_time - now, count)));
+
+    let (duration_secs, winner_count) = match parsed {
+        Ok(parsed) => parsed,
+        Err(why) => {
             modal.create_response(ctx, serenity::CreateInteractionResponse::Message(
                 serenity::CreateInteractionResponseMessage::new()
-                    .content("Format de durée invalide. Utilisez 1j, 1h, 30m.")
+                    .content(format!("❌ {}", why))
                     .ephemeral(true)
             )).await?;
             return Ok(());
         }
     };
 
-    let winner_count = winners_str.parse::<i32>().unwrap_or(1);
-    let end_time = chrono::Utc::now().timestamp() + duration_secs;
+    let giveaway = NewGiveaway {
+        channel_id: modal.channel_id,
+        host_id: modal.user.id,
+        title,
+        description,
+        reward,
+        winner_count,
+        duration_secs,
+        ping_role_id: None,
+    };
 
-    let embed = serenity::CreateEmbed::new()
-        .title(&title)
-        .description(&description)
-        .field("Récompense", &reward, true)
+    launch_giveaway(ctx, data, &giveaway).await?;
+
+    modal.create_response(ctx, serenity::CreateInteractionResponse::Message(
+        serenity::CreateInteractionResponseMessage::new()
+            .content("Giveaway créé avec succès.")
+            .ephemeral(true)
+    )).await?;
+
+    Ok(())
+}
+
+pub fn active_embed(title: &str, description: &str, reward: &str, winner_count: i32, end_time: i64, participants: i64) -> serenity::CreateEmbed {
+    serenity::CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .field("Récompense", reward, true)
         .field("Gagnants", winner_count.to_string(), true)
         .field("Fin", format!("<t:{}:R>", end_time), true)
         .color(0x3498db)
-        .footer(serenity::CreateEmbedFooter::new("Participants: 0"));
+        .footer(serenity::CreateEmbedFooter::new(format!("Participants: {}", participants)))
+}
+
+pub async fn launch_giveaway(
+    ctx: &serenity::Context,
+    data: &Data,
+    giveaway: &NewGiveaway,
+) -> Result<serenity::Message, crate::Error> {
+    let db = &data.db;
+
+    let end_time = chrono::Utc::now().timestamp() + giveaway.duration_secs;
+
+    let embed = active_embed(&giveaway.title, &giveaway.description, &giveaway.reward, giveaway.winner_count, end_time, 0);
 
     let button = serenity::CreateButton::new("giveaway_join")
         .label("Participer")
         .style(serenity::ButtonStyle::Primary);
 
-    modal.create_response(ctx, serenity::CreateInteractionResponse::Message(
-        serenity::CreateInteractionResponseMessage::new()
-            .embed(embed)
-            .components(vec![serenity::CreateActionRow::Buttons(vec![button])])
-    )).await?;
+    let mut builder = serenity::CreateMessage::new()
+        .embed(embed)
+        .components(vec![serenity::CreateActionRow::Buttons(vec![button])]);
 
-    let message = modal.get_response(ctx).await?;
-    let message_id = message.id.get() as i64;
-    let channel_id = modal.channel_id.get() as i64;
-    let host_id = modal.user.id.get() as i64;
+    if let Some(role_id) = giveaway.ping_role_id {
+        builder = builder.content(format!("<@&{}>", role_id));
+    }
+
+    let message = giveaway.channel_id.send_message(ctx, builder).await?;
 
     sqlx::query(
         "INSERT INTO giveaways (message_id, channel_id, host_id, title, description, reward, winner_count, end_time, status)
          VALUES (?, ?, ?, ?, ?, ?, ?, ?, 'active')"
     )
-    .bind(message_id)
-    .bind(channel_id)
-    .bind(host_id)
-    .bind(&title)
-    .bind(&description)
-    .bind(&reward)
-    .bind(winner_count)
+    .bind(message.id.get() as i64)
+    .bind(giveaway.channel_id.get() as i64)
+    .bind(giveaway.host_id.get() as i64)
+    .bind(&giveaway.title)
+    .bind(&giveaway.description)
+    .bind(&giveaway.reward)
+    .bind(giveaway.winner_count)
     .bind(end_time)
-    .execute(&data.db)
+    .execute(db)
     .await?;
 
     let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
     let log_embed = serenity::CreateEmbed::new()
         .title("Nouveau Giveaway Créé")
-        .field("Titre", &title, false)
-        .field("Créateur", format!("<@{}>", host_id), true)
-        .field("Salon", format!("<#{}>", channel_id), true)
-        .field("Récompense", &reward, true)
-        .field("Durée", &duration_str, true)
-        .field("Gagnants", winner_count.to_string(), true)
+        .field("Titre", &giveaway.title, false)
+        .field("Créateur", format!("<@{}>", giveaway.host_id), true)
+        .field("Salon", format!("<#{}>", giveaway.channel_id), true)
+        .field("Récompense", &giveaway.reward, true)
+        .field("Fin", format!("<t:{}:f>", end_time), true)
+        .field("Gagnants", giveaway.winner_count.to_string(), true)
         .color(0x2ecc71)
         .timestamp(serenity::Timestamp::now());
     
     let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(log_embed)).await;
 
-    Ok(())
+    data.giveaway_scheduler.wake();
+
+    Ok(message)
 }
 
-fn parse_duration(input: &str) -> Option<i64> {
-    let mut total_seconds = 0;
-    let parts: Vec<&str> = input.split(',').collect();
-    
-    for part in parts {
-        let part = part.trim().to_lowercase();
-        if part.is_empty() { continue; }
-        
-        let val_str: String = part.chars().filter(|c| c.is_digit(10)).collect();
-        if val_str.is_empty() { continue; }
-        let val: i64 = val_str.parse().ok()?;
-        
-        let unit_str: String = part.chars().filter(|c| !c.is_digit(10) && !c.is_whitespace()).collect();
-        
-        if unit_str.starts_with('j') || unit_str.starts_with('d') {
-            total_seconds += val * 86400;
-        } else if unit_str.starts_with('h') {
-            total_seconds += val * 3600;
-        } else if unit_str.starts_with('m') {
-            total_seconds += val * 60;
-        } else if unit_str.starts_with('s') {
-            total_seconds += val;
+pub async fn schedule_giveaway(
+    ctx: &serenity::Context,
+    data: &Data,
+    giveaway: &NewGiveaway,
+    start_at: i64,
+) -> Result<i64, crate::Error> {
+    let db = &data.db;
+
+    let result = sqlx::query(
+        "INSERT INTO scheduled_giveaways (channel_id, host_id, title, description, reward, winner_count, duration_secs, ping_role_id, start_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(giveaway.channel_id.get() as i64)
+    .bind(giveaway.host_id.get() as i64)
+    .bind(&giveaway.title)
+    .bind(&giveaway.description)
+    .bind(&giveaway.reward)
+    .bind(giveaway.winner_count)
+    .bind(giveaway.duration_secs)
+    .bind(giveaway.ping_role_id.map(|id| id.get() as i64))
+    .bind(start_at)
+    .execute(db)
+    .await?;
+
+    let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
+    let log_embed = serenity::CreateEmbed::new()
+        .title("Giveaway Programmé")
+        .field("Titre", &giveaway.title, false)
+        .field("Créateur", format!("<@{}>", giveaway.host_id), true)
+        .field("Salon", format!("<#{}>", giveaway.channel_id), true)
+        .field("Début", format!("<t:{}:f>", start_at), true)
+        .color(0x9b59b6)
+        .timestamp(serenity::Timestamp::now());
+
+    let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(log_embed)).await;
+
+    data.giveaway_scheduler.wake();
+
+    Ok(result.last_insert_rowid())
+}
+
+type ScheduledGiveawayRow = (i64, i64, i64, String, String, String, i32, i64, Option<i64>);
+
+async fn launch_scheduled_giveaways(ctx: &serenity::Context, data: &Data) {
+    let db = &data.db;
+
+    let due: Vec<ScheduledGiveawayRow> = sqlx::query_as(
+        "SELECT id, channel_id, host_id, title, description, reward, winner_count, duration_secs, ping_role_id
+         FROM scheduled_giveaways WHERE start_at <= ?"
+    )
+    .bind(chrono::Utc::now().timestamp())
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+
+    for (id, channel_id, host_id, title, description, reward, winner_count, duration_secs, ping_role_id) in due {
+        let claimed = sqlx::query("DELETE FROM scheduled_giveaways WHERE id = ?")
+            .bind(id)
+            .execute(db)
+            .await
+            .map(|r| r.rows_affected() > 0)
+            .unwrap_or(false);
+
+        if !claimed {
+            continue;
+        }
+
+        let giveaway = NewGiveaway {
+            channel_id: serenity::ChannelId::new(channel_id as u64),
+            host_id: serenity::UserId::new(host_id as u64),
+            title,
+            description,
+            reward,
+            winner_count,
+            duration_secs,
+            ping_role_id: ping_role_id.map(|id| serenity::RoleId::new(id as u64)),
+        };
+
+        if let Err(why) = launch_giveaway(ctx, data, &giveaway).await {
+            eprintln!("Erreur lors du lancement du giveaway programmé {} : {:?}", id, why);
         }
     }
-    
-    if total_seconds == 0 { return None; }
-    Some(total_seconds)
 }
 
-pub async fn check_giveaways(db: &sqlx::Pool<sqlx::Sqlite>, http: &serenity::Http, log_channel_id: u64) {
+fn draw_winners(participants: &[(i64, i64)], count: usize, exclude: &[i64]) -> Vec<i64> {
+    let eligible: Vec<(i64, i64)> = participants.iter()
+        .filter(|(user_id, _)| !exclude.contains(user_id))
+        .cloned()
+        .collect();
+
+    let mut rng = rand::rng();
+
+    match eligible.choose_multiple_weighted(&mut rng, count, |(_, weight)| (*weight).max(1) as f64) {
+        Ok(winners) => winners.map(|(user_id, _)| *user_id).collect(),
+        Err(_) => eligible.choose_multiple(&mut rng, count).map(|(user_id, _)| *user_id).collect(),
+    }
+}
+
+async fn participants(conn: &mut sqlx::SqliteConnection, message_id: i64) -> Result<Vec<(i64, i64)>, sqlx::Error> {
+    sqlx::query_as("SELECT user_id, weight FROM giveaway_participants WHERE giveaway_message_id = ?")
+        .bind(message_id)
+        .fetch_all(conn)
+        .await
+}
+
+async fn previous_winners(conn: &mut sqlx::SqliteConnection, message_id: i64) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar("SELECT user_id FROM giveaway_winners WHERE giveaway_message_id = ?")
+        .bind(message_id)
+        .fetch_all(conn)
+        .await
+}
+
+async fn record_winners(conn: &mut sqlx::SqliteConnection, data: &Data, message_id: i64, winners: &[i64]) -> Result<Option<i64>, sqlx::Error> {
     let now = chrono::Utc::now().timestamp();
+    let window = data.config.giveaways.claim_window_secs;
+    let claim_deadline = (window > 0).then_some(now + window);
+
+    for winner_id in winners {
+        sqlx::query("INSERT OR IGNORE INTO giveaway_winners (giveaway_message_id, user_id, won_at, claim_deadline) VALUES (?, ?, ?, ?)")
+            .bind(message_id)
+            .bind(winner_id)
+            .bind(now)
+            .bind(claim_deadline)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    Ok(claim_deadline)
+}
+
+async fn announce_winners(
+    ctx: &serenity::Context,
+    channel: serenity::ChannelId,
+    message_id: i64,
+    (title, reward): (&str, &str),
+    winners: &[i64],
+    claim_deadline: Option<i64>,
+    reroll: bool,
+) -> String {
+    let winner_mentions: Vec<String> = winners.iter().map(|id| format!("<@{}>", id)).collect();
+    let winners_text = winner_mentions.join(", ");
+
+    let description = if reroll {
+        format!("Nouveau tirage pour le giveaway **{}** !\n\n**Gagnant(s):** {}\n**Récompense:** {}", title, winners_text, reward)
+    } else {
+        format!("Le giveaway **{}** est terminé !\n\n**Gagnant(s):** {}\n**Récompense:** {}", title, winners_text, reward)
+    };
+
+    let win_embed = serenity::CreateEmbed::new()
+        .title("Félicitations !")
+        .description(description)
+        .color(0xf1c40f)
+        .timestamp(serenity::Timestamp::now());
+
+    let claim_button = serenity::CreateButton::new(format!("giveaway_claim:{}", message_id))
+        .label("Réclamer mon lot")
+        .emoji('🎁')
+        .style(serenity::ButtonStyle::Success);
+
+    let mut announcement = serenity::CreateMessage::new().content(&winners_text).embed(win_embed);
+    if claim_deadline.is_some() {
+        announcement = announcement.components(vec![serenity::CreateActionRow::Buttons(vec![claim_button.clone()])]);
+    }
+
+    let _ = channel.send_message(ctx, announcement).await;
+
+    for winner_id in winners {
+        let user = serenity::UserId::new(*winner_id as u64);
+        if let Ok(dm) = user.create_dm_channel(ctx).await {
+            let message = match claim_deadline {
+                Some(deadline) => serenity::CreateMessage::new()
+                    .content(format!(
+                        "Bravo ! Vous avez gagné le giveaway **{}** pour **{}**. Cliquez sur le bouton ci-dessous pour réclamer votre lot avant <t:{}:f>, sinon un autre gagnant sera tiré.",
+                        title, reward, deadline
+                    ))
+                    .components(vec![serenity::CreateActionRow::Buttons(vec![claim_button.clone()])]),
+                None => serenity::CreateMessage::new()
+                    .content(format!("Bravo ! Vous avez gagné le giveaway **{}** pour **{}**. Un staff vous contactera bientôt.", title, reward)),
+            };
+            let _ = dm.send_message(ctx, message).await;
+        }
+    }
+
+    winners_text
+}
+
+type GiveawayRow = (i64, String, String, String, i32, i64, String);
+
+async fn load_giveaway(db: &sqlx::Pool<sqlx::Sqlite>, message_id: i64) -> Result<Option<GiveawayRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT channel_id, title, description, reward, winner_count, end_time, status FROM giveaways WHERE message_id = ?"
+    )
+    .bind(message_id)
+    .fetch_optional(db)
+    .await
+}
+
+async fn draw_eligible_winners(
+    ctx: &serenity::Context,
+    data: &Data,
+    message_id: i64,
+    channel_id: i64,
+    count: usize,
+    exclude: &[i64],
+) -> Result<Vec<i64>, crate::Error> {
+    let participants = participants(&mut *data.db.acquire().await?, message_id).await?;
+    let order = draw_winners(&participants, participants.len(), exclude);
+
+    let guild_id = serenity::ChannelId::new(channel_id as u64).to_channel(ctx).await.ok()
+        .and_then(|channel| channel.guild())
+        .map(|channel| channel.guild_id);
+
+    let Some(guild_id) = guild_id else {
+        return Ok(order.into_iter().take(count).collect());
+    };
+
+    let mut winners = Vec::new();
+    for user_id in order {
+        if winners.len() >= count {
+            break;
+        }
+
+        let Ok(member) = guild_id.member(ctx, serenity::UserId::new(user_id as u64)).await else {
+            continue;
+        };
+
+        if requirements::check(&data.db, message_id, &member).await?.is_none() {
+            winners.push(user_id);
+        }
+    }
+
+    Ok(winners)
+}
+
+pub async fn end_giveaway(
+    ctx: &serenity::Context,
+    data: &Data,
+    message_id: i64,
+    ended_by: Option<serenity::UserId>,
+) -> Result<bool, crate::Error> {
+    let Some((channel_id, _, _, _, winner_count, _, status)) = load_giveaway(&data.db, message_id).await? else {
+        return Ok(false);
+    };
+
+    if status != "active" {
+        return Ok(false);
+    }
+
+    let winners = draw_eligible_winners(ctx, data, message_id, channel_id, winner_count as usize, &[]).await?;
+
+    let mut tx = data.db.begin().await?;
+
+    let ended = sqlx::query(
+        "UPDATE giveaways SET status = 'ended', end_time = MIN(end_time, ?), announced = 0 WHERE message_id = ? AND status = 'active'"
+    )
+    .bind(chrono::Utc::now().timestamp())
+    .bind(message_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected() > 0;
+
+    if !ended {
+        return Ok(false);
+    }
+
+    let claim_deadline = record_winners(&mut tx, data, message_id, &winners).await?;
+
+    tx.commit().await?;
+
+    if claim_deadline.is_some() {
+        data.giveaway_scheduler.wake();
+    }
+
+    announce_ending(ctx, data, message_id, ended_by).await?;
+
+    Ok(true)
+}
+
+async fn announce_ending(
+    ctx: &serenity::Context,
+    data: &Data,
+    message_id: i64,
+    ended_by: Option<serenity::UserId>,
+) -> Result<(), crate::Error> {
+    let db = &data.db;
+
+    let Some((channel_id, title, description, reward, winner_count, end_time, _)) = load_giveaway(db, message_id).await? else {
+        return Ok(());
+    };
+
+    let participant_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM giveaway_participants WHERE giveaway_message_id = ?")
+        .bind(message_id)
+        .fetch_one(db)
+        .await?;
+
+    let channel = serenity::ChannelId::new(channel_id as u64);
+    let message_id_serenity = serenity::MessageId::new(message_id as u64);
+
+    if let Ok(mut msg) = channel.message(ctx, message_id_serenity).await {
+        let new_embed = serenity::CreateEmbed::new()
+            .title(format!("Giveaway Terminé : {}", title))
+            .description(description)
+            .field("Récompense", reward.clone(), true)
+            .field("Gagnants", winner_count.to_string(), true)
+            .field("Fin", format!("<t:{}:f>", end_time), true)
+            .color(0x95a5a6)
+            .footer(serenity::CreateEmbedFooter::new(format!("Participants: {}", participant_count)));
+        
+        let _ = msg.edit(ctx, serenity::EditMessage::new()
+            .embed(new_embed)
+            .components(vec![])
+        ).await;
+    }
+
+    let mut tx = db.begin().await?;
+
+    let claimed = sqlx::query("UPDATE giveaways SET announced = 1 WHERE message_id = ? AND announced = 0")
+        .bind(message_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected() > 0;
+
+    if !claimed {
+        return Ok(());
+    }
+
+    let window = data.config.giveaways.claim_window_secs;
+    if window > 0 {
+        sqlx::query(
+            "UPDATE giveaway_winners SET claim_deadline = ? WHERE giveaway_message_id = ? AND claimed_at IS NULL AND forfeited = 0"
+        )
+        .bind(chrono::Utc::now().timestamp() + window)
+        .bind(message_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let winners: Vec<(i64, Option<i64>)> = sqlx::query_as(
+        "SELECT user_id, claim_deadline FROM giveaway_winners WHERE giveaway_message_id = ? ORDER BY won_at"
+    )
+    .bind(message_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let claim_deadline = winners.iter().filter_map(|(_, deadline)| *deadline).max();
+    let winners: Vec<i64> = winners.into_iter().map(|(user_id, _)| user_id).collect();
+
+    let winners_text = if winners.is_empty() {
+        let _ = channel.say(ctx, format!("Le giveaway **{}** est terminé. Aucun participant.", title)).await;
+        "Aucun participant".to_string()
+    } else {
+        announce_winners(ctx, channel, message_id, (&title, &reward), &winners, claim_deadline, false).await
+    };
+
+    let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
+    let mut log_embed = serenity::CreateEmbed::new()
+        .title("Giveaway Terminé")
+        .field("Titre", title, false)
+        .field("Gagnant(s)", winners_text, false)
+        .field("Participants", participant_count.to_string(), true)
+        .color(0xf1c40f)
+        .timestamp(serenity::Timestamp::now());
+
+    if let Some(user_id) = ended_by {
+        log_embed = log_embed.field("Terminé manuellement par", format!("<@{}>", user_id), true);
+    }
     
-    let ended_giveaways: Vec<(i64, i64, i64, String, String, String, i32, i64)> = sqlx::query_as(
-        "SELECT message_id, channel_id, host_id, title, description, reward, winner_count, end_time FROM giveaways WHERE status = 'active' AND end_time <= ?"
+    let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(log_embed)).await;
+
+    Ok(())
+}
+
+async fn announce_pending_endings(ctx: &serenity::Context, data: &Data) {
+    let now = chrono::Utc::now().timestamp();
+
+    let pending: Vec<i64> = sqlx::query_scalar(
+        "SELECT message_id FROM giveaways WHERE status = 'ended' AND announced = 0 AND end_time <= ?"
     )
-    .bind(now)
-    .fetch_all(db)
+    .bind(now - ANNOUNCE_GRACE_SECS)
+    .fetch_all(&data.db)
     .await
     .unwrap_or_default();
 
-    for (message_id, channel_id, _host_id, title, description, reward, winner_count, end_time) in ended_giveaways {
-        let _ = sqlx::query("UPDATE giveaways SET status = 'ended' WHERE message_id = ?")
+    for message_id in pending {
+        if !data.giveaway_scheduler.should_attempt("announce", message_id, now) {
+            continue;
+        }
+
+        match announce_ending(ctx, data, message_id, None).await {
+            Ok(()) => data.giveaway_scheduler.succeeded("announce", message_id),
+            Err(why) => {
+                data.giveaway_scheduler.failed("announce", message_id, now);
+                eprintln!("Erreur lors de l'annonce du giveaway {} : {:?}", message_id, why);
+            }
+        }
+    }
+}
+
+async fn mark_replaced(conn: &mut sqlx::SqliteConnection, message_id: i64, users: &[i64]) -> Result<(), sqlx::Error> {
+    for user_id in users {
+        sqlx::query("UPDATE giveaway_winners SET replaced = 1 WHERE giveaway_message_id = ? AND user_id = ?")
+            .bind(message_id)
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn reroll_giveaway(
+    ctx: &serenity::Context,
+    data: &Data,
+    message_id: i64,
+    count: usize,
+    rerolled_by: Option<serenity::UserId>,
+    replacing: &[i64],
+) -> Result<Vec<i64>, crate::Error> {
+    let db = &data.db;
+
+    let Some((channel_id, title, _, reward, _, _, status)) = load_giveaway(db, message_id).await? else {
+        return Ok(Vec::new());
+    };
+
+    if status != "ended" {
+        return Ok(Vec::new());
+    }
+
+    let previous = previous_winners(&mut *db.acquire().await?, message_id).await?;
+
+    let winners = draw_eligible_winners(ctx, data, message_id, channel_id, count, &previous).await?;
+    if winners.is_empty() {
+        mark_replaced(&mut *db.acquire().await?, message_id, replacing).await?;
+        return Ok(winners);
+    }
+
+    let mut tx = db.begin().await?;
+
+    sqlx::query("UPDATE giveaways SET status = status WHERE message_id = ?")
+        .bind(message_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut current = previous_winners(&mut tx, message_id).await?;
+    let mut expected = previous.clone();
+    current.sort_unstable();
+    expected.sort_unstable();
+
+    if current != expected {
+        return Ok(Vec::new());
+    }
+
+    let claim_deadline = record_winners(&mut tx, data, message_id, &winners).await?;
+    mark_replaced(&mut tx, message_id, replacing).await?;
+    tx.commit().await?;
+
+    if claim_deadline.is_some() {
+        data.giveaway_scheduler.wake();
+    }
+
+    let channel = serenity::ChannelId::new(channel_id as u64);
+    let winners_text = announce_winners(ctx, channel, message_id, (&title, &reward), &winners, claim_deadline, true).await;
+    let rerolled_by = match rerolled_by {
+        Some(user_id) => format!("<@{}>", user_id),
+        None => "Automatique (lot non réclamé)".to_string(),
+    };
+
+    let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
+    let log_embed = serenity::CreateEmbed::new()
+        .title("Giveaway Relancé")
+        .field("Titre", title, false)
+        .field("Nouveau(x) gagnant(s)", winners_text, false)
+        .field("Relancé par", rerolled_by, true)
+        .field("Anciens gagnants exclus", previous.len().to_string(), true)
+        .color(0xe67e22)
+        .timestamp(serenity::Timestamp::now());
+
+    let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(log_embed)).await;
+
+    Ok(winners)
+}
+
+pub async fn cancel_giveaway(
+    ctx: &serenity::Context,
+    data: &Data,
+    message_id: i64,
+    cancelled_by: serenity::UserId,
+) -> Result<bool, crate::Error> {
+    let db = &data.db;
+
+    let giveaway: Option<(i64, i64, String)> = sqlx::query_as(
+        "SELECT channel_id, host_id, title FROM giveaways WHERE message_id = ? AND status = 'active'"
+    )
+    .bind(message_id)
+    .fetch_optional(db)
+    .await?;
+
+    let Some((channel_id, host_id, title)) = giveaway else {
+        return Ok(false);
+    };
+
+    for table in ["giveaway_participants", "giveaway_roles", "giveaway_bonus_roles"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE giveaway_message_id = ?", table))
             .bind(message_id)
             .execute(db)
-            .await;
+            .await?;
+    }
+
+    sqlx::query("DELETE FROM giveaways WHERE message_id = ?")
+        .bind(message_id)
+        .execute(db)
+        .await?;
+
+    let _ = serenity::ChannelId::new(channel_id as u64).delete_message(ctx, serenity::MessageId::new(message_id as u64)).await;
+
+    let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
+    let embed = serenity::CreateEmbed::new()
+        .title("Giveaway Supprimé")
+        .field("Titre", title, false)
+        .field("Supprimé par", format!("<@{}>", cancelled_by), true)
+        .field("Créé par", format!("<@{}>", host_id), true)
+        .color(0xe74c3c)
+        .timestamp(serenity::Timestamp::now());
+    
+    let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(embed)).await;
+
+    Ok(true)
+}
+
+pub async fn unschedule_giveaway(
+    ctx: &serenity::Context,
+    data: &Data,
+    id: i64,
+    cancelled_by: serenity::UserId,
+) -> Result<bool, crate::Error> {
+    let db = &data.db;
+
+    let scheduled: Option<(i64, String, i64)> = sqlx::query_as(
+        "SELECT host_id, title, start_at FROM scheduled_giveaways WHERE id = ?"
+    )
+    .bind(id)
+    .fetch_optional(db)
+    .await?;
+
+    let Some((host_id, title, start_at)) = scheduled else {
+        return Ok(false);
+    };
 
-        let participants: Vec<i64> = sqlx::query_scalar(
-            "SELECT user_id FROM giveaway_participants WHERE giveaway_message_id = ?"
+    sqlx::query("DELETE FROM scheduled_giveaways WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await?;
+
+    let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
+    let embed = serenity::CreateEmbed::new()
+        .title("Giveaway Programmé Annulé")
+        .field("Titre", title, false)
+        .field("Annulé par", format!("<@{}>", cancelled_by), true)
+        .field("Créé par", format!("<@{}>", host_id), true)
+        .field("Début prévu", format!("<t:{}:f>", start_at), true)
+        .color(0xe74c3c)
+        .timestamp(serenity::Timestamp::now());
+
+    let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(embed)).await;
+
+    Ok(true)
+}
+
+async fn claim_prize(
+    ctx: &serenity::Context,
+    component: &serenity::ComponentInteraction,
+    data: &Data,
+    message_id: i64,
+) -> Result<(), crate::Error> {
+    let user_id = component.user.id;
+
+    let winner: Option<(Option<i64>, Option<i64>, bool)> = sqlx::query_as(
+        "SELECT claim_deadline, claimed_at, forfeited FROM giveaway_winners WHERE giveaway_message_id = ? AND user_id = ?"
+    )
+    .bind(message_id)
+    .bind(user_id.get() as i64)
+    .fetch_optional(&data.db)
+    .await?;
+
+    let now = chrono::Utc::now().timestamp();
+    let refusal = match winner {
+        None => Some("Vous n'êtes pas gagnant de ce giveaway."),
+        Some((_, Some(_), _)) => Some("Vous avez déjà réclamé ce lot."),
+        Some((_, _, true)) => Some("Le délai pour réclamer ce lot est dépassé, un autre gagnant a été tiré."),
+        Some((Some(deadline), _, _)) if deadline <= now => Some("Le délai pour réclamer ce lot est dépassé."),
+        _ => None,
+    };
+
+    if let Some(refusal) = refusal {
+        component.create_response(ctx, serenity::CreateInteractionResponse::Message(
+            serenity::CreateInteractionResponseMessage::new()
+                .content(refusal)
+                .ephemeral(true)
+        )).await?;
+        return Ok(());
+    }
+
+    component.defer_ephemeral(ctx).await?;
+
+    let claimed = sqlx::query(
+        "UPDATE giveaway_winners SET claimed_at = ? WHERE giveaway_message_id = ? AND user_id = ? AND claimed_at IS NULL AND forfeited = 0"
+    )
+    .bind(now)
+    .bind(message_id)
+    .bind(user_id.get() as i64)
+    .execute(&data.db)
+    .await?
+    .rows_affected() > 0;
+
+    if !claimed {
+        component.edit_response(ctx, serenity::EditInteractionResponse::new().content("Vous avez déjà réclamé ce lot.")).await?;
+        return Ok(());
+    }
+
+    let Some((channel_id, title, _, reward, _, _, _)) = load_giveaway(&data.db, message_id).await? else {
+        return Ok(());
+    };
+
+    let mut content = format!("🎁 Lot **{}** réclamé ! Un membre du staff va vous contacter.", reward);
+
+    if data.config.giveaways.open_ticket_on_claim {
+        let details = format!(
+            "Réclamation du giveaway **{}**\nRécompense : **{}**\nGiveaway : https://discord.com/channels/{}/{}/{}",
+            title,
+            reward,
+            std::env::var("DISCORD_GUILD_ID").unwrap_or_default(),
+            channel_id,
+            message_id
+        );
+
+        let existing: Option<i64> = sqlx::query_scalar("SELECT channel_id FROM tickets WHERE user_id = ?")
+            .bind(user_id.get() as i64)
+            .fetch_optional(&data.db)
+            .await?;
+
+        let ticket_channel = match existing {
+            Some(channel_id) => {
+                let channel_id = serenity::ChannelId::new(channel_id as u64);
+                channel_id.say(ctx, &details).await?;
+                Some(channel_id)
+            }
+            None => match crate::ticket_system::events::create_ticket(ctx, data, &component.user, &details, "fr", TicketCategory::Other).await {
+                Ok(channel) => Some(channel.id),
+                Err(why) => {
+                    eprintln!("Erreur lors de l'ouverture du ticket de réclamation pour {} : {:?}", user_id, why);
+                    None
+                }
+            },
+        };
+
+        if let Some(ticket_channel) = ticket_channel {
+            content = format!("🎁 Lot **{}** réclamé ! Un ticket a été ouvert pour organiser la remise : <#{}>", reward, ticket_channel);
+        }
+    }
+
+    component.edit_response(ctx, serenity::EditInteractionResponse::new().content(content)).await?;
+
+    let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
+    let log_embed = serenity::CreateEmbed::new()
+        .title("Lot Réclamé")
+        .field("Titre", title, false)
+        .field("Gagnant", format!("<@{}>", user_id), true)
+        .field("Récompense", reward, true)
+        .color(0x2ecc71)
+        .timestamp(serenity::Timestamp::now());
+
+    let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(log_embed)).await;
+
+    Ok(())
+}
+
+async fn expire_unclaimed_prizes(ctx: &serenity::Context, data: &Data) {
+    let expired: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT giveaway_message_id, user_id FROM giveaway_winners
+         WHERE claimed_at IS NULL AND forfeited = 0 AND claim_deadline IS NOT NULL AND claim_deadline <= ?"
+    )
+    .bind(chrono::Utc::now().timestamp())
+    .fetch_all(&data.db)
+    .await
+    .unwrap_or_default();
+
+    let mut forfeits: Vec<(i64, Vec<i64>)> = Vec::new();
+
+    for (message_id, user_id) in expired {
+        let forfeited = sqlx::query(
+            "UPDATE giveaway_winners SET forfeited = 1, replaced = 0 WHERE giveaway_message_id = ? AND user_id = ? AND claimed_at IS NULL AND forfeited = 0"
         )
         .bind(message_id)
-        .fetch_all(db)
+        .bind(user_id)
+        .execute(&data.db)
         .await
-        .unwrap_or_default();
-
-        let channel = serenity::ChannelId::new(channel_id as u64);
-        let message_id_serenity = serenity::MessageId::new(message_id as u64);
-
-        if let Ok(mut msg) = channel.message(http, message_id_serenity).await {
-            let new_embed = serenity::CreateEmbed::new()
-                .title(format!("Giveaway Terminé : {}", title))
-                .description(description)
-                .field("Récompense", reward.clone(), true)
-                .field("Gagnants", winner_count.to_string(), true)
-                .field("Fin", format!("<t:{}:f>", end_time), true)
-                .color(0x95a5a6)
-                .footer(serenity::CreateEmbedFooter::new(format!("Participants: {}", participants.len())));
-            
-            let _ = msg.edit(http, serenity::EditMessage::new()
-                .embed(new_embed)
-                .components(vec![])
-            ).await;
-        }
+        .map(|r| r.rows_affected() > 0)
+        .unwrap_or(false);
 
-        if participants.is_empty() {
-            let _ = channel.say(http, format!("Le giveaway **{}** est terminé. Aucun participant.", title)).await;
+        if !forfeited {
             continue;
         }
 
-        let winners: Vec<_> = {
-            let mut rng = rand::rng();
-            participants
-                .choose_multiple(&mut rng, winner_count as usize)
-                .cloned()
-                .collect()
-        };
+        match forfeits.iter_mut().find(|(id, _)| *id == message_id) {
+            Some((_, users)) => users.push(user_id),
+            None => forfeits.push((message_id, vec![user_id])),
+        }
+    }
 
-        let winner_mentions: Vec<String> = winners.iter().map(|id| format!("<@{}>", id)).collect();
-        let winners_text = winner_mentions.join(", ");
+    for (message_id, users) in forfeits {
+        let mentions: Vec<String> = users.iter().map(|id| format!("<@{}>", id)).collect();
+        let title = load_giveaway(&data.db, message_id).await
+            .ok()
+            .flatten()
+            .map(|(_, title, ..)| title)
+            .unwrap_or_else(|| message_id.to_string());
 
-        let win_embed = serenity::CreateEmbed::new()
-            .title("Félicitations !")
-            .description(format!("Le giveaway **{}** est terminé !\n\n**Gagnant(s):** {}\n**Récompense:** {}", title, winners_text, reward))
-            .color(0xf1c40f)
+        let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
+        let log_embed = serenity::CreateEmbed::new()
+            .title("Lot Non Réclamé")
+            .field("Titre", title, false)
+            .field("Gagnant(s) déchu(s)", mentions.join(", "), false)
+            .color(0x95a5a6)
             .timestamp(serenity::Timestamp::now());
 
-        let _ = channel.send_message(http, serenity::CreateMessage::new().content(&winners_text).embed(win_embed)).await;
+        let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(log_embed)).await;
+    }
+}
+
+async fn replace_forfeited_winners(ctx: &serenity::Context, data: &Data) {
+    let pending: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT giveaway_message_id, user_id FROM giveaway_winners WHERE forfeited = 1 AND replaced = 0"
+    )
+    .fetch_all(&data.db)
+    .await
+    .unwrap_or_default();
+
+    let mut by_giveaway: Vec<(i64, Vec<i64>)> = Vec::new();
+    for (message_id, user_id) in pending {
+        match by_giveaway.iter_mut().find(|(id, _)| *id == message_id) {
+            Some((_, users)) => users.push(user_id),
+            None => by_giveaway.push((message_id, vec![user_id])),
+        }
+    }
 
-        for winner_id in &winners {
-            let user = serenity::UserId::new(*winner_id as u64);
-            if let Ok(dm) = user.create_dm_channel(http).await {
-                let _ = dm.say(http, format!("Bravo ! Vous avez gagné le giveaway **{}** pour **{}**. Un staff vous contactera bientôt.", title, reward)).await;
+    let now = chrono::Utc::now().timestamp();
+
+    for (message_id, users) in by_giveaway {
+        if !data.giveaway_scheduler.should_attempt("reroll", message_id, now) {
+            continue;
+        }
+
+        match reroll_giveaway(ctx, data, message_id, users.len(), None, &users).await {
+            Ok(_) => data.giveaway_scheduler.succeeded("reroll", message_id),
+            Err(why) => {
+                data.giveaway_scheduler.failed("reroll", message_id, now);
+                eprintln!("Erreur lors du nouveau tirage du giveaway {} : {:?}", message_id, why);
             }
         }
+    }
+}
 
-        let log_channel = serenity::ChannelId::new(log_channel_id);
-        let log_embed = serenity::CreateEmbed::new()
-            .title("Giveaway Terminé")
-            .field("Titre", title, false)
-            .field("Gagnant(s)", winners_text, false)
-            .field("Participants", participants.len().to_string(), true)
-            .color(0xf1c40f)
-            .timestamp(serenity::Timestamp::now());
-        
-        let _ = log_channel.send_message(http, serenity::CreateMessage::new().embed(log_embed)).await;
+pub async fn check_giveaways(ctx: &serenity::Context, data: &Data) {
+    launch_scheduled_giveaways(ctx, data).await;
+    expire_unclaimed_prizes(ctx, data).await;
+    replace_forfeited_winners(ctx, data).await;
+    announce_pending_endings(ctx, data).await;
+
+    let db = &data.db;
+    let now = chrono::Utc::now().timestamp();
+    
+    let ended_giveaways: Vec<i64> = sqlx::query_scalar(
+        "SELECT message_id FROM giveaways WHERE status = 'active' AND end_time <= ?"
+    )
+    .bind(now)
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+
+    for message_id in ended_giveaways {
+        if !data.giveaway_scheduler.should_attempt("end", message_id, now) {
+            continue;
+        }
+
+        match end_giveaway(ctx, data, message_id, None).await {
+            Ok(_) => data.giveaway_scheduler.succeeded("end", message_id),
+            Err(why) => {
+                data.giveaway_scheduler.failed("end", message_id, now);
+                eprintln!("Erreur lors de la fin du giveaway {} : {:?}", message_id, why);
+            }
+        }
     }
 }
@@ -0,0 +1,167 @@
+use poise::serenity_prelude as serenity;
+use crate::Data;
+
+const PER_PAGE: i64 = 5;
+const FIELD_NAME_MAX: usize = 100;
+const FIELD_VALUE_MAX: usize = 900;
+const RECENT_WINS_SECS: i64 = 30 * 86400;
+const LISTED_WINS: usize = 10;
+
+type WinnerRow = (i64, Option<i64>, Option<i64>, bool);
+type WinRow = (i64, String, String, i64, Option<i64>, Option<i64>, bool);
+
+fn claim_status(claim_deadline: Option<i64>, claimed_at: Option<i64>, forfeited: bool) -> &'static str {
+    if forfeited {
+        " (non réclamé)"
+    } else if claimed_at.is_some() {
+        " ✅"
+    } else if claim_deadline.is_some() {
+        " ⏳"
+    } else {
+        ""
+    }
+}
+
+pub async fn history_page(
+    db: &sqlx::Pool<sqlx::Sqlite>,
+    guild_id: Option<serenity::GuildId>,
+    page: i64,
+) -> Result<(serenity::CreateEmbed, Vec<serenity::CreateActionRow>), crate::Error> {
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM giveaways WHERE status = 'ended'")
+        .fetch_one(db)
+        .await?;
+
+    let total_pages = ((total + PER_PAGE - 1) / PER_PAGE).max(1);
+    let page = page.clamp(0, total_pages - 1);
+
+    let giveaways: Vec<(i64, i64, String, String, i64)> = sqlx::query_as(
+        "SELECT message_id, channel_id, title, reward, end_time FROM giveaways WHERE status = 'ended'
+         ORDER BY end_time DESC LIMIT ? OFFSET ?"
+    )
+    .bind(PER_PAGE)
+    .bind(page * PER_PAGE)
+    .fetch_all(db)
+    .await?;
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title("Historique des giveaways")
+        .footer(serenity::CreateEmbedFooter::new(format!("Page {}/{} • {} giveaway(s) terminé(s)", page + 1, total_pages, total)))
+        .color(0x3498db);
+
+    if giveaways.is_empty() {
+        embed = embed.description("Aucun giveaway terminé pour le moment.");
+    }
+
+    for (message_id, channel_id, title, reward, end_time) in giveaways {
+        let participants: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM giveaway_participants WHERE giveaway_message_id = ?")
+            .bind(message_id)
+            .fetch_one(db)
+            .await?;
+
+        let winners: Vec<WinnerRow> = sqlx::query_as(
+            "SELECT user_id, claim_deadline, claimed_at, forfeited FROM giveaway_winners WHERE giveaway_message_id = ? ORDER BY won_at"
+        )
+        .bind(message_id)
+        .fetch_all(db)
+        .await?;
+
+        let winners_text = if winners.is_empty() {
+            "Aucun gagnant".to_string()
+        } else {
+            winners.iter()
+                .map(|(user_id, deadline, claimed_at, forfeited)| format!("<@{}>{}", user_id, claim_status(*deadline, *claimed_at, *forfeited)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let link = match guild_id {
+            Some(guild_id) => format!("[Voir](https://discord.com/channels/{}/{}/{})", guild_id, channel_id, message_id),
+            None => format!("<#{}>", channel_id),
+        };
+
+        let value = format!(
+            "**Récompense :** {}\n**Fin :** <t:{}:f> • {} participant(s) • {}\n**Gagnant(s) :** {}",
+            reward, end_time, participants, link, winners_text
+        );
+
+        embed = embed.field(title.chars().take(FIELD_NAME_MAX).collect::<String>(), value.chars().take(FIELD_VALUE_MAX).collect::<String>(), false);
+    }
+
+    let buttons = vec![serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(format!("giveaway_history_page:{}", (page - 1).max(0)))
+            .label("◀")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(page == 0),
+        serenity::CreateButton::new(format!("giveaway_history_page:{}", page + 1))
+            .label("▶")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(page + 1 >= total_pages),
+    ])];
+
+    Ok((embed, buttons))
+}
+
+pub async fn wins_embed(db: &sqlx::Pool<sqlx::Sqlite>, user: &serenity::User) -> Result<serenity::CreateEmbed, crate::Error> {
+    let wins: Vec<WinRow> = sqlx::query_as(
+        "SELECT g.channel_id, g.title, g.reward, w.won_at, w.claim_deadline, w.claimed_at, w.forfeited
+         FROM giveaway_winners w JOIN giveaways g ON g.message_id = w.giveaway_message_id
+         WHERE w.user_id = ? ORDER BY w.won_at DESC"
+    )
+    .bind(user.id.get() as i64)
+    .fetch_all(db)
+    .await?;
+
+    let since = chrono::Utc::now().timestamp() - RECENT_WINS_SECS;
+    let valid: Vec<&WinRow> = wins.iter().filter(|w| !w.6).collect();
+    let recent = valid.iter().filter(|w| w.3 >= since).count();
+    let forfeited = wins.len() - valid.len();
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(format!("Victoires de {}", user.name))
+        .thumbnail(user.face())
+        .field("Victoires", valid.len().to_string(), true)
+        .field("30 derniers jours", recent.to_string(), true)
+        .field("Lots non réclamés", forfeited.to_string(), true)
+        .color(0xf1c40f);
+
+    if let Some(last) = valid.first() {
+        embed = embed.field("Dernière victoire", format!("<t:{}:f> (<t:{}:R>)", last.3, last.3), false);
+    }
+
+    let listed = wins.iter()
+        .take(LISTED_WINS)
+        .map(|(channel_id, title, reward, won_at, deadline, claimed_at, forfeited)| {
+            format!("• <t:{}:d> — **{}** ({}) dans <#{}>{}", won_at, title, reward, channel_id, claim_status(*deadline, *claimed_at, *forfeited))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let description = if listed.is_empty() {
+        "Aucune victoire enregistrée.".to_string()
+    } else if wins.len() > LISTED_WINS {
+        format!("{}\n… et {} autre(s)", listed, wins.len() - LISTED_WINS)
+    } else {
+        listed
+    };
+
+    Ok(embed.description(description.chars().take(4000).collect::<String>()))
+}
+
+pub async fn handle_component(
+    ctx: &serenity::Context,
+    component: &serenity::ComponentInteraction,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let page = component.data.custom_id
+        .strip_prefix("giveaway_history_page:")
+        .and_then(|page| page.parse().ok())
+        .unwrap_or(0);
+
+    let (embed, components) = history_page(&data.db, component.guild_id, page).await?;
+
+    component.create_response(ctx, serenity::CreateInteractionResponse::UpdateMessage(
+        serenity::CreateInteractionResponseMessage::new().embed(embed).components(components)
+    )).await?;
+
+    Ok(())
+}
@@ -0,0 +1,455 @@
+use poise::serenity_prelude as serenity;
+use crate::Data;
+use crate::giveaway_system::events;
+
+const PER_PAGE: usize = 10;
+const ENDED_VISIBLE_SECS: i64 = 7 * 86400;
+
+type EntryRow = (String, i64, i64, String, i64);
+
+async fn list_entries(db: &sqlx::Pool<sqlx::Sqlite>) -> Result<Vec<EntryRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT kind, id, channel_id, title, at FROM (
+            SELECT 'active' AS kind, 0 AS rank, message_id AS id, channel_id, title, end_time AS at FROM giveaways WHERE status = 'active'
+            UNION ALL
+            SELECT 'scheduled', 1, id, channel_id, title, start_at FROM scheduled_giveaways
+            UNION ALL
+            SELECT 'ended', 2, message_id, channel_id, title, end_time FROM giveaways WHERE status = 'ended' AND end_time >= ?
+        ) ORDER BY rank, CASE WHEN rank = 2 THEN -at ELSE at END"
+    )
+    .bind(chrono::Utc::now().timestamp() - ENDED_VISIBLE_SECS)
+    .fetch_all(db)
+    .await
+}
+
+fn kind_label(kind: &str) -> &'static str {
+    match kind {
+        "active" => "🟢 En cours",
+        "scheduled" => "🕒 Programmé",
+        _ => "🏁 Terminé",
+    }
+}
+
+fn back_button() -> serenity::CreateButton {
+    serenity::CreateButton::new("giveaway_manage_page:0").label("Retour à la liste").style(serenity::ButtonStyle::Secondary)
+}
+
+async fn list_message(db: &sqlx::Pool<sqlx::Sqlite>, page: usize) -> Result<serenity::CreateInteractionResponseMessage, crate::Error> {
+    let entries = list_entries(db).await?;
+    let total_pages = entries.len().div_ceil(PER_PAGE).max(1);
+    let page = page.min(total_pages - 1);
+    let slice = &entries[(page * PER_PAGE).min(entries.len())..((page + 1) * PER_PAGE).min(entries.len())];
+
+    let description = if slice.is_empty() {
+        "Aucun giveaway en cours ou programmé.".to_string()
+    } else {
+        slice.iter()
+            .map(|(kind, _, channel_id, title, at)| {
+                let when = if kind == "scheduled" { "début" } else { "fin" };
+                format!("{} — **{}** dans <#{}> ({} <t:{}:R>)", kind_label(kind), title, channel_id, when, at)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = serenity::CreateEmbed::new()
+        .title("Giveaways")
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(format!("Page {}/{}", page + 1, total_pages)))
+        .color(0x3498db);
+
+    let mut components = Vec::new();
+
+    if !slice.is_empty() {
+        let options = slice.iter()
+            .map(|(kind, id, _, title, _)| {
+                let label: String = title.chars().take(90).collect();
+                serenity::CreateSelectMenuOption::new(label, format!("{}:{}", kind, id))
+                    .description(kind_label(kind))
+            })
+            .collect();
+
+        components.push(serenity::CreateActionRow::SelectMenu(
+            serenity::CreateSelectMenu::new("giveaway_manage_select", serenity::CreateSelectMenuKind::String { options })
+                .placeholder("Choisir un giveaway à gérer")
+        ));
+    }
+
+    components.push(serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(format!("giveaway_manage_page:{}", page.saturating_sub(1)))
+            .label("◀")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(page == 0),
+        serenity::CreateButton::new(format!("giveaway_manage_page:{}", page + 1))
+            .label("▶")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(page + 1 >= total_pages),
+    ]));
+
+    Ok(serenity::CreateInteractionResponseMessage::new()
+        .content("")
+        .embed(embed)
+        .components(components))
+}
+
+async fn details_message(
+    db: &sqlx::Pool<sqlx::Sqlite>,
+    guild_id: Option<serenity::GuildId>,
+    kind: &str,
+    id: i64,
+) -> Result<serenity::CreateInteractionResponseMessage, crate::Error> {
+    let mut buttons = Vec::new();
+
+    let embed = if kind == "scheduled" {
+        let row: Option<(i64, i64, String, String, i32, i64, i64)> = sqlx::query_as(
+            "SELECT channel_id, host_id, title, reward, winner_count, duration_secs, start_at FROM scheduled_giveaways WHERE id = ?"
+        )
+        .bind(id)
+        .fetch_optional(db)
+        .await?;
+
+        let Some((channel_id, host_id, title, reward, winner_count, duration_secs, start_at)) = row else {
+            return Ok(notice("Ce giveaway n'existe plus."));
+        };
+
+        buttons.push(serenity::CreateButton::new(format!("giveaway_manage_unschedule:{}", id)).label("Annuler").style(serenity::ButtonStyle::Danger));
+
+        serenity::CreateEmbed::new()
+            .title(title)
+            .field("Statut", kind_label(kind), true)
+            .field("Salon", format!("<#{}>", channel_id), true)
+            .field("Créé par", format!("<@{}>", host_id), true)
+            .field("Récompense", reward, true)
+            .field("Gagnants", winner_count.to_string(), true)
+            .field("Début", format!("<t:{}:f>", start_at), true)
+            .field("Fin prévue", format!("<t:{}:f>", start_at + duration_secs), true)
+            .color(0x9b59b6)
+    } else {
+        let row: Option<(i64, i64, String, String, i32, i64, String)> = sqlx::query_as(
+            "SELECT channel_id, host_id, title, reward, winner_count, end_time, status FROM giveaways WHERE message_id = ?"
+        )
+        .bind(id)
+        .fetch_optional(db)
+        .await?;
+
+        let Some((channel_id, host_id, title, reward, winner_count, end_time, status)) = row else {
+            return Ok(notice("Ce giveaway n'existe plus."));
+        };
+
+        let participants: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM giveaway_participants WHERE giveaway_message_id = ?")
+            .bind(id)
+            .fetch_one(db)
+            .await?;
+
+        let mut embed = serenity::CreateEmbed::new()
+            .title(title)
+            .field("Statut", kind_label(&status), true)
+            .field("Salon", format!("<#{}>", channel_id), true)
+            .field("Créé par", format!("<@{}>", host_id), true)
+            .field("Récompense", reward, true)
+            .field("Gagnants", winner_count.to_string(), true)
+            .field("Fin", format!("<t:{}:f>", end_time), true)
+            .field("Participants", participants.to_string(), true)
+            .color(0x3498db);
+
+        if let Some(guild_id) = guild_id {
+            embed = embed.url(format!("https://discord.com/channels/{}/{}/{}", guild_id, channel_id, id));
+        }
+
+        if status == "active" {
+            buttons.push(serenity::CreateButton::new(format!("giveaway_manage_edit:{}", id)).label("Modifier").style(serenity::ButtonStyle::Secondary));
+            buttons.push(serenity::CreateButton::new(format!("giveaway_manage_end:{}", id)).label("Terminer").style(serenity::ButtonStyle::Primary));
+            buttons.push(serenity::CreateButton::new(format!("giveaway_manage_cancel:{}", id)).label("Annuler").style(serenity::ButtonStyle::Danger));
+        } else {
+            let winners: Vec<i64> = sqlx::query_scalar("SELECT user_id FROM giveaway_winners WHERE giveaway_message_id = ?")
+                .bind(id)
+                .fetch_all(db)
+                .await?;
+
+            if !winners.is_empty() {
+                let mentions: Vec<String> = winners.iter().map(|w| format!("<@{}>", w)).collect();
+                embed = embed.field("Gagnant(s)", mentions.join(", "), false);
+            }
+
+            buttons.push(serenity::CreateButton::new(format!("giveaway_manage_reroll:{}", id)).label("Relancer un gagnant").style(serenity::ButtonStyle::Primary));
+        }
+
+        embed
+    };
+
+    buttons.push(back_button());
+
+    Ok(serenity::CreateInteractionResponseMessage::new()
+        .content("")
+        .embed(embed)
+        .components(vec![serenity::CreateActionRow::Buttons(buttons)]))
+}
+
+fn notice(content: &str) -> serenity::CreateInteractionResponseMessage {
+    serenity::CreateInteractionResponseMessage::new()
+        .content(content)
+        .embeds(vec![])
+        .components(vec![serenity::CreateActionRow::Buttons(vec![back_button()])])
+}
+
+pub async fn handle_component(
+    ctx: &serenity::Context,
+    component: &serenity::ComponentInteraction,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let staff_role = serenity::RoleId::new(data.config.roles.staff_role_id);
+    if !component.member.as_ref().is_some_and(|m| m.roles.contains(&staff_role)) {
+        component.create_response(ctx, serenity::CreateInteractionResponse::Message(
+            serenity::CreateInteractionResponseMessage::new()
+                .content("Vous n'avez pas la permission.")
+                .ephemeral(true)
+        )).await?;
+        return Ok(());
+    }
+
+    let custom_id = component.data.custom_id.as_str();
+    let (action, arg) = custom_id.split_once(':').unwrap_or((custom_id, ""));
+
+    match action {
+        "giveaway_manage_open" => {
+            let message = list_message(&data.db, 0).await?.ephemeral(true);
+            component.create_response(ctx, serenity::CreateInteractionResponse::Message(message)).await?;
+        }
+        "giveaway_manage_page" => {
+            let message = list_message(&data.db, arg.parse().unwrap_or(0)).await?;
+            component.create_response(ctx, serenity::CreateInteractionResponse::UpdateMessage(message)).await?;
+        }
+        "giveaway_manage_select" => {
+            let serenity::ComponentInteractionDataKind::StringSelect { values } = &component.data.kind else {
+                return Ok(());
+            };
+            let Some((kind, id)) = values.first().and_then(|v| v.split_once(':')) else {
+                return Ok(());
+            };
+
+            let message = details_message(&data.db, component.guild_id, kind, id.parse().unwrap_or_default()).await?;
+            component.create_response(ctx, serenity::CreateInteractionResponse::UpdateMessage(message)).await?;
+        }
+        "giveaway_manage_edit" => {
+            let id: i64 = arg.parse().unwrap_or_default();
+            let row: Option<(String, String, String, i32)> = sqlx::query_as(
+                "SELECT title, description, reward, winner_count FROM giveaways WHERE message_id = ? AND status = 'active'"
+            )
+            .bind(id)
+            .fetch_optional(&data.db)
+            .await?;
+
+            let Some((title, description, reward, winner_count)) = row else {
+                component.create_response(ctx, serenity::CreateInteractionResponse::UpdateMessage(notice("Ce giveaway n'est plus en cours."))).await?;
+                return Ok(());
+            };
+
+            let mut description_input = serenity::CreateInputText::new(serenity::InputTextStyle::Paragraph, "Description", "description")
+                .required(false);
+            if !description.is_empty() {
+                description_input = description_input.value(description);
+            }
+
+            let modal = serenity::CreateModal::new(format!("giveaway_edit_modal:{}", id), "Modifier le Giveaway")
+                .components(vec![
+                    serenity::CreateActionRow::InputText(serenity::CreateInputText::new(
+                        serenity::InputTextStyle::Short, "Titre", "title"
+                    ).value(title)),
+                    serenity::CreateActionRow::InputText(description_input),
+                    serenity::CreateActionRow::InputText(serenity::CreateInputText::new(
+                        serenity::InputTextStyle::Short, "Récompense", "reward"
+                    ).value(reward)),
+                    serenity::CreateActionRow::InputText(serenity::CreateInputText::new(
+                        serenity::InputTextStyle::Short, "Nombre de gagnants", "winners"
+                    ).value(winner_count.to_string())),
+                    serenity::CreateActionRow::InputText(serenity::CreateInputText::new(
+                        serenity::InputTextStyle::Short, "Nouvelle durée ou date de fin", "duration"
+                    ).placeholder("Laisser vide pour garder la fin actuelle").required(false)),
+                ]);
+
+            component.create_response(ctx, serenity::CreateInteractionResponse::Modal(modal)).await?;
+        }
+        "giveaway_manage_end" | "giveaway_manage_cancel" | "giveaway_manage_reroll" | "giveaway_manage_unschedule" => {
+            let id: i64 = arg.parse().unwrap_or_default();
+            component.create_response(ctx, serenity::CreateInteractionResponse::Acknowledge).await?;
+
+            let outcome = match action {
+                "giveaway_manage_end" => {
+                    if events::end_giveaway(ctx, data, id, Some(component.user.id)).await? {
+                        "✅ Giveaway terminé, les gagnants ont été tirés."
+                    } else {
+                        "Ce giveaway n'est plus en cours."
+                    }
+                }
+                "giveaway_manage_cancel" => {
+                    if events::cancel_giveaway(ctx, data, id, component.user.id).await? {
+                        "🗑️ Giveaway annulé et supprimé."
+                    } else {
+                        "Ce giveaway n'est plus en cours."
+                    }
+                }
+                "giveaway_manage_reroll" => {
+                    if events::reroll_giveaway(ctx, data, id, 1, Some(component.user.id), &[]).await?.is_empty() {
+                        "Aucun autre participant éligible pour un nouveau tirage."
+                    } else {
+                        "🎲 Nouveau gagnant tiré."
+                    }
+                }
+                _ => {
+                    if events::unschedule_giveaway(ctx, data, id, component.user.id).await? {
+                        "🗑️ Giveaway programmé annulé."
+                    } else {
+                        "Ce giveaway programmé n'existe plus."
+                    }
+                }
+            };
+
+            component.edit_response(ctx, serenity::EditInteractionResponse::new()
+                .content(outcome)
+                .embeds(vec![])
+                .components(vec![serenity::CreateActionRow::Buttons(vec![back_button()])])
+            ).await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+pub async fn handle_edit_modal(
+    ctx: &serenity::Context,
+    modal: &serenity::ModalInteraction,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let id: i64 = modal.data.custom_id
+        .trim_start_matches("giveaway_edit_modal:")
+        .parse()
+        .unwrap_or_default();
+
+    let mut title = String::new();
+    let mut description = String::new();
+    let mut reward = String::new();
+    let mut winners_str = String::new();
+    let mut duration_str = String::new();
+
+    for row in &modal.data.components {
+        if let Some(serenity::ActionRowComponent::InputText(input)) = row.components.first() {
+            let value = input.value.clone().unwrap_or_default();
+            match input.custom_id.as_str() {
+                "title" => title = value,
+                "description" => description = value,
+                "reward" => reward = value,
+                "winners" => winners_str = value,
+                "duration" => duration_str = value,
+                _ => {}
+            }
+        }
+    }
+
+    let reply = |content: String| serenity::CreateInteractionResponse::Message(
+        serenity::CreateInteractionResponseMessage::new().content(content).ephemeral(true)
+    );
+
+    let winner_count = match events::parse_winner_count(&winners_str) {
+        Ok(count) => count,
+        Err(why) => {
+            modal.create_response(ctx, reply(format!("❌ {}", why))).await?;
+            return Ok(());
+        }
+    };
+
+    let row: Option<(i64, String, String, String, i32, i64)> = sqlx::query_as(
+        "SELECT channel_id, title, description, reward, winner_count, end_time FROM giveaways WHERE message_id = ? AND status = 'active'"
+    )
+    .bind(id)
+    .fetch_optional(&data.db)
+    .await?;
+
+    let Some((channel_id, old_title, old_description, old_reward, old_winner_count, old_end_time)) = row else {
+        modal.create_response(ctx, reply("Ce giveaway n'est plus en cours.".to_string())).await?;
+        return Ok(());
+    };
+
+    let end_time = if duration_str.trim().is_empty() {
+        old_end_time
+    } else {
+        match events::giveaway_end_time(&duration_str, chrono::Utc::now().timestamp()) {
+            Ok(end_time) => end_time,
+            Err(why) => {
+                modal.create_response(ctx, reply(format!("❌ {}", why))).await?;
+                return Ok(());
+            }
+        }
+    };
+
+    let mut changes = Vec::new();
+    if old_title != title {
+        changes.push(("Titre", old_title, title.clone()));
+    }
+    if old_description != description {
+        changes.push(("Description", old_description, description.clone()));
+    }
+    if old_reward != reward {
+        changes.push(("Récompense", old_reward, reward.clone()));
+    }
+    if old_winner_count != winner_count {
+        changes.push(("Gagnants", old_winner_count.to_string(), winner_count.to_string()));
+    }
+    if old_end_time != end_time {
+        changes.push(("Fin", format!("<t:{}:f>", old_end_time), format!("<t:{}:f>", end_time)));
+    }
+
+    if changes.is_empty() {
+        modal.create_response(ctx, reply("Aucune modification.".to_string())).await?;
+        return Ok(());
+    }
+
+    let updated = sqlx::query(
+        "UPDATE giveaways SET title = ?, description = ?, reward = ?, winner_count = ?, end_time = ? WHERE message_id = ? AND status = 'active'"
+    )
+    .bind(&title)
+    .bind(&description)
+    .bind(&reward)
+    .bind(winner_count)
+    .bind(end_time)
+    .bind(id)
+    .execute(&data.db)
+    .await?
+    .rows_affected() > 0;
+
+    if !updated {
+        modal.create_response(ctx, reply("Ce giveaway n'est plus en cours.".to_string())).await?;
+        return Ok(());
+    }
+
+    data.giveaway_scheduler.wake();
+
+    let participants: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM giveaway_participants WHERE giveaway_message_id = ?")
+        .bind(id)
+        .fetch_one(&data.db)
+        .await?;
+
+    let embed = events::active_embed(&title, &description, &reward, winner_count, end_time, participants);
+    let channel = serenity::ChannelId::new(channel_id as u64);
+    let _ = channel.edit_message(ctx, serenity::MessageId::new(id as u64), serenity::EditMessage::new().embed(embed)).await;
+
+    modal.create_response(ctx, reply(format!("✅ Giveaway **{}** modifié.", title))).await?;
+
+    let mut log_embed = serenity::CreateEmbed::new()
+        .title("Giveaway Modifié")
+        .field("Giveaway", format!("https://discord.com/channels/{}/{}/{}", modal.guild_id.unwrap_or_default(), channel_id, id), false)
+        .field("Modifié par", format!("<@{}>", modal.user.id), true)
+        .color(0xf39c12)
+        .timestamp(serenity::Timestamp::now());
+
+    for (field, before, after) in changes {
+        let shorten = |value: String| if value.is_empty() { "*(vide)*".to_string() } else { value.chars().take(450).collect() };
+        let (before, after) = (shorten(before), shorten(after));
+        log_embed = log_embed.field(field, format!("**Avant :** {}\n**Après :** {}", before, after), false);
+    }
+
+    let log_channel = serenity::ChannelId::new(data.config.channels.giveaway_log_channel_id);
+    let _ = log_channel.send_message(ctx, serenity::CreateMessage::new().embed(log_embed)).await;
+
+    Ok(())
+}
@@ -1 +1,6 @@
 pub mod events;
+pub mod history;
+pub mod manage;
+pub mod requirements;
+pub mod scheduler;
+pub mod structs;
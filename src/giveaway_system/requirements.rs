@@ -0,0 +1,135 @@
+use poise::serenity_prelude as serenity;
+use sqlx::{Pool, Sqlite};
+use crate::voice_system::activity;
+
+#[derive(Debug, Default, sqlx::FromRow)]
+pub struct Requirements {
+    pub min_member_days: Option<i64>,
+    pub min_account_days: Option<i64>,
+    pub min_voice_minutes: Option<i64>,
+    pub min_messages: Option<i64>,
+}
+
+pub async fn load(db: &Pool<Sqlite>, message_id: i64) -> Result<Requirements, sqlx::Error> {
+    let requirements = sqlx::query_as(
+        "SELECT min_member_days, min_account_days, min_voice_minutes, min_messages FROM giveaways WHERE message_id = ?"
+    )
+    .bind(message_id)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(requirements.unwrap_or_default())
+}
+
+pub async fn load_roles(db: &Pool<Sqlite>, message_id: i64) -> Result<Vec<(serenity::RoleId, bool)>, sqlx::Error> {
+    let rows: Vec<(i64, bool)> = sqlx::query_as(
+        "SELECT role_id, required FROM giveaway_roles WHERE giveaway_message_id = ?"
+    )
+    .bind(message_id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().map(|(role_id, required)| (serenity::RoleId::new(role_id as u64), required)).collect())
+}
+
+pub async fn load_bonus_roles(db: &Pool<Sqlite>, message_id: i64) -> Result<Vec<(serenity::RoleId, i64)>, sqlx::Error> {
+    let rows: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT role_id, entries FROM giveaway_bonus_roles WHERE giveaway_message_id = ? ORDER BY entries DESC"
+    )
+    .bind(message_id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().map(|(role_id, entries)| (serenity::RoleId::new(role_id as u64), entries)).collect())
+}
+
+pub async fn entry_weight(db: &Pool<Sqlite>, message_id: i64, member: &serenity::Member) -> Result<i64, sqlx::Error> {
+    let weight = load_bonus_roles(db, message_id).await?
+        .into_iter()
+        .filter(|(role_id, _)| member.roles.contains(role_id))
+        .map(|(_, entries)| entries)
+        .max()
+        .unwrap_or(1);
+
+    Ok(weight.max(1))
+}
+
+pub async fn record_message(db: &Pool<Sqlite>, user_id: serenity::UserId) {
+    let _ = sqlx::query(
+        "INSERT INTO message_counts (user_id, count) VALUES (?, 1)
+         ON CONFLICT(user_id) DO UPDATE SET count = count + 1"
+    )
+    .bind(user_id.get() as i64)
+    .execute(db)
+    .await;
+}
+
+async fn message_count(db: &Pool<Sqlite>, user_id: serenity::UserId) -> Result<i64, sqlx::Error> {
+    let count: Option<i64> = sqlx::query_scalar("SELECT count FROM message_counts WHERE user_id = ?")
+        .bind(user_id.get() as i64)
+        .fetch_optional(db)
+        .await?;
+
+    Ok(count.unwrap_or(0))
+}
+
+pub async fn check(
+    db: &Pool<Sqlite>,
+    message_id: i64,
+    member: &serenity::Member,
+) -> Result<Option<String>, crate::Error> {
+    for (role_id, required) in load_roles(db, message_id).await? {
+        let has_role = member.roles.contains(&role_id);
+        if required && !has_role {
+            return Ok(Some(format!("Vous devez avoir le rôle <@&{}> pour participer à ce giveaway.", role_id)));
+        }
+        if !required && has_role {
+            return Ok(Some(format!("Les membres ayant le rôle <@&{}> ne peuvent pas participer à ce giveaway.", role_id)));
+        }
+    }
+
+    let requirements = load(db, message_id).await?;
+    let now = chrono::Utc::now().timestamp();
+
+    if let Some(days) = requirements.min_member_days {
+        let joined_at = member.joined_at.map(|t| t.unix_timestamp()).unwrap_or(now);
+        if now - joined_at < days * 86400 {
+            return Ok(Some(format!(
+                "Vous devez être membre du serveur depuis au moins {} jour(s) pour participer (arrivée <t:{}:R>).",
+                days, joined_at
+            )));
+        }
+    }
+
+    if let Some(days) = requirements.min_account_days {
+        let created_at = member.user.id.created_at().unix_timestamp();
+        if now - created_at < days * 86400 {
+            return Ok(Some(format!(
+                "Votre compte Discord doit avoir au moins {} jour(s) pour participer (créé <t:{}:R>).",
+                days, created_at
+            )));
+        }
+    }
+
+    if let Some(minutes) = requirements.min_voice_minutes {
+        let total = activity::user_total(db, member.user.id, activity::Period::All).await?;
+        if total < minutes * 60 {
+            return Ok(Some(format!(
+                "Vous devez avoir passé au moins {} en vocal pour participer (actuellement {}).",
+                activity::format_duration(minutes * 60), activity::format_duration(total)
+            )));
+        }
+    }
+
+    if let Some(required) = requirements.min_messages {
+        let count = message_count(db, member.user.id).await?;
+        if count < required {
+            return Ok(Some(format!(
+                "Vous devez avoir envoyé au moins {} messages sur le serveur pour participer (actuellement {}).",
+                required, count
+            )));
+        }
+    }
+
+    Ok(None)
+}
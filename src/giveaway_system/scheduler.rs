@@ -0,0 +1,82 @@
+use poise::serenity_prelude as serenity;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::Notify;
+use crate::Data;
+use super::events;
+
+const MAX_SLEEP_SECS: i64 = 3600;
+const RETRY_BASE_SECS: i64 = 5;
+
+type RetryState = HashMap<(&'static str, i64), (u32, i64)>;
+
+#[derive(Default)]
+pub struct GiveawayScheduler {
+    wakeup: Notify,
+    retries: Mutex<RetryState>,
+}
+
+impl GiveawayScheduler {
+    pub fn wake(&self) {
+        self.wakeup.notify_one();
+    }
+
+    pub fn should_attempt(&self, task: &'static str, id: i64, now: i64) -> bool {
+        self.retries.lock().unwrap().get(&(task, id)).is_none_or(|(_, retry_at)| *retry_at <= now)
+    }
+
+    pub fn succeeded(&self, task: &'static str, id: i64) {
+        self.retries.lock().unwrap().remove(&(task, id));
+    }
+
+    pub fn failed(&self, task: &'static str, id: i64, now: i64) {
+        let mut retries = self.retries.lock().unwrap();
+        let (attempts, retry_at) = retries.entry((task, id)).or_insert((0, now));
+        *attempts += 1;
+        *retry_at = now + (RETRY_BASE_SECS << (*attempts - 1).min(10)).min(MAX_SLEEP_SECS);
+    }
+
+    fn next_retry(&self) -> Option<i64> {
+        self.retries.lock().unwrap().values().map(|(_, retry_at)| *retry_at).min()
+    }
+}
+
+async fn next_deadline(db: &sqlx::Pool<sqlx::Sqlite>, after: i64) -> Option<i64> {
+    sqlx::query_scalar(
+        "SELECT MIN(deadline) FROM (
+            SELECT MIN(end_time) AS deadline FROM giveaways WHERE status = 'active' AND end_time > ?1
+            UNION ALL
+            SELECT MIN(start_at) FROM scheduled_giveaways WHERE start_at > ?1
+            UNION ALL
+            SELECT MIN(claim_deadline) FROM giveaway_winners
+            WHERE claimed_at IS NULL AND forfeited = 0 AND claim_deadline > ?1
+        )"
+    )
+    .bind(after)
+    .fetch_one(db)
+    .await
+    .ok()
+    .flatten()
+}
+
+pub async fn run(ctx: serenity::Context, data: Data) {
+    loop {
+        let started = chrono::Utc::now().timestamp();
+        events::check_giveaways(&ctx, &data).await;
+
+        let now = chrono::Utc::now().timestamp();
+        let next = [next_deadline(&data.db, started).await, data.giveaway_scheduler.next_retry()]
+            .into_iter()
+            .flatten()
+            .min();
+        let wait = next
+            .map(|deadline| (deadline - now).clamp(1, MAX_SLEEP_SECS))
+            .unwrap_or(MAX_SLEEP_SECS);
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(wait as u64)) => {}
+            _ = data.giveaway_scheduler.wakeup.notified() => {}
+        }
+    }
+}
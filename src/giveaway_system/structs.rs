@@ -0,0 +1,13 @@
+use poise::serenity_prelude as serenity;
+
+#[derive(Debug, Clone)]
+pub struct NewGiveaway {
+    pub channel_id: serenity::ChannelId,
+    pub host_id: serenity::UserId,
+    pub title: String,
+    pub description: String,
+    pub reward: String,
+    pub winner_count: i32,
+    pub duration_secs: i64,
+    pub ping_role_id: Option<serenity::RoleId>,
+}
@@ -1,6 +1,7 @@
 mod commands;
 mod config;
 mod database;
+mod duration;
 mod ticket_system;
 mod voice_system;
 mod giveaway_system;
@@ -13,16 +14,21 @@ use std::sync::{Arc, Mutex};
 use sysinfo::System;
 use std::collections::HashMap;
 use ticket_system::structs::TicketState;
+use voice_system::locks::VoiceLocks;
+use giveaway_system::scheduler::GiveawayScheduler;
 use config::Config;
 use tokio::sync::RwLock;
 use sqlx::{Pool, Sqlite};
 
+#[derive(Clone)]
 pub struct Data {
     pub start_time: Instant,
     pub system_info: Arc<Mutex<System>>,
     pub config: Config,
     pub db: Pool<Sqlite>, 
     pub ticket_states: Arc<RwLock<HashMap<u64, TicketState>>>,
+    pub voice_locks: Arc<VoiceLocks>,
+    pub giveaway_scheduler: Arc<GiveawayScheduler>,
 }
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -41,7 +47,9 @@ async fn main() {
     let intents = serenity::GatewayIntents::non_privileged() 
         | serenity::GatewayIntents::DIRECT_MESSAGES 
         | serenity::GatewayIntents::MESSAGE_CONTENT 
-        | serenity::GatewayIntents::GUILD_MESSAGES; 
+        | serenity::GatewayIntents::GUILD_MESSAGES
+        | serenity::GatewayIntents::DIRECT_MESSAGE_TYPING
+        | serenity::GatewayIntents::GUILD_MESSAGE_TYPING; 
 
     let config = Config::load().expect("Impossible de charger config.toml");
     
@@ -55,11 +63,17 @@ async fn main() {
                 commands::ticket::rep(),
                 commands::ticket::close(),
                 commands::ticket::rename(),
+                commands::ticket::tag(),
+                commands::ticket::tickets(),
+                commands::ticket::remind(),
+                commands::ticket::followup(),
                 commands::moderation::clear(),
                 commands::moderation::blticket(),
                 commands::moderation::unblticket(),
                 commands::profile::profil(),
                 commands::giveaway::giveaway(),
+                commands::panel::panel(),
+                commands::voice::voice(),
             ],
             event_handler: |ctx, event, framework, data| {
                 Box::pin(async move {
@@ -89,6 +103,8 @@ async fn main() {
                     config: config.clone(),
                     db: db.clone(),
                     ticket_states: Arc::new(RwLock::new(HashMap::new())),
+                    voice_locks: Arc::new(VoiceLocks::default()),
+                    giveaway_scheduler: Arc::new(GiveawayScheduler::default()),
                 };
 
                 let db_clone = data.db.clone();
@@ -102,18 +118,41 @@ async fn main() {
                     }
                 });
 
-                let db_clone_gw = data.db.clone();
-                let http_clone_gw = ctx.http.clone();
-                let log_gw = config.channels.giveaway_log_channel_id;
-                
+                let ctx_clone_vc = ctx.clone();
+                let data_clone_vc = data.clone();
+
+                tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(600));
+                    interval.tick().await;
+                    loop {
+                        interval.tick().await;
+                        voice_system::events::reconcile_voice_channels(&ctx_clone_vc, &data_clone_vc).await;
+                    }
+                });
+
+                let db_clone_va = data.db.clone();
+
+                tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+                    loop {
+                        interval.tick().await;
+                        voice_system::activity::checkpoint(&db_clone_va).await;
+                    }
+                });
+
+                let db_clone_rm = data.db.clone();
+                let http_clone_rm = ctx.http.clone();
+
                 tokio::spawn(async move {
-                    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5)); 
+                    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
                     loop {
                         interval.tick().await;
-                        giveaway_system::events::check_giveaways(&db_clone_gw, &http_clone_gw, log_gw).await;
+                        ticket_system::events::check_ticket_reminders(&db_clone_rm, &http_clone_rm).await;
                     }
                 });
 
+                tokio::spawn(giveaway_system::scheduler::run(ctx.clone(), data.clone()));
+
                 Ok(data)
             })
         })
@@ -147,10 +186,7 @@ async fn check_inactive_tickets(
     .unwrap_or_default();
     
     for (uid, channel_id) in tickets_to_close {
-        let _ = sqlx::query("DELETE FROM tickets WHERE user_id = ?")
-            .bind(uid)
-            .execute(db)
-            .await;
+        let _ = ticket_system::events::delete_ticket_records(db, uid, channel_id).await;
             
         let _ = serenity::ChannelId::new(channel_id as u64).delete(http).await;
         
@@ -1,6 +1,10 @@
 use poise::serenity_prelude as serenity;
 use crate::Data;
 use crate::ticket_system::structs::{TicketState, TicketCategory};
+use crate::config::TicketMode;
+
+const RECEIPT_PENDING: &str = "📨";
+const RECEIPT_ANSWERED: &str = "✅";
 
 pub async fn handle_event(
     ctx: &serenity::Context,
@@ -19,10 +23,23 @@ pub async fn handle_event(
             }
         }
         serenity::FullEvent::InteractionCreate { interaction } => {
-            if let serenity::Interaction::Component(component) = interaction {
-                handle_component(ctx, component, data).await?;
+            match interaction {
+                serenity::Interaction::Component(component) => {
+                    if component.data.custom_id.starts_with("panel_") {
+                        handle_panel_component(ctx, component, data).await?;
+                    } else {
+                        handle_component(ctx, component, data).await?;
+                    }
+                }
+                serenity::Interaction::Modal(modal) => {
+                    handle_panel_modal(ctx, modal, data).await?;
+                }
+                _ => {}
             }
         }
+        serenity::FullEvent::TypingStart { event } => {
+            handle_typing(ctx, event, data).await?;
+        }
         _ => {}
     }
     Ok(())
@@ -67,10 +84,17 @@ async fn handle_dm(
         let content = format!("**{}**: {}", msg.author.name, msg.content);
         channel_id.say(ctx, content).await?;
         
-        msg.react(ctx, serenity::ReactionType::Unicode("✅".to_string())).await?;
+        msg.react(ctx, serenity::ReactionType::Unicode(RECEIPT_PENDING.to_string())).await?;
+
+        sqlx::query("INSERT OR IGNORE INTO ticket_receipts (message_id, user_id, dm_channel_id) VALUES (?, ?, ?)")
+            .bind(msg.id.get() as i64)
+            .bind(user_id as i64)
+            .bind(msg.channel_id.get() as i64)
+            .execute(&data.db)
+            .await?;
 
         sqlx::query(
-            "UPDATE tickets SET last_activity = ?, has_been_reminded = 0 WHERE user_id = ?"
+            "UPDATE tickets SET last_activity = ?, has_been_reminded = 0, awaiting_staff = 1 WHERE user_id = ?"
         )
         .bind(chrono::Utc::now().timestamp())
         .bind(user_id as i64)
@@ -87,7 +111,8 @@ async fn handle_dm(
 
     match state {
         Some(TicketState::WritingMessage { language, category }) => {
-            create_ticket(ctx, msg, data, language, category).await?;
+            create_ticket(ctx, data, &msg.author, &msg.content, &language, category).await?;
+            msg.channel_id.say(ctx, confirmation_message(&language)).await?;
             
             let mut states = data.ticket_states.write().await;
             states.remove(&user_id);
@@ -120,6 +145,151 @@ async fn handle_dm(
     Ok(())
 }
 
+async fn handle_typing(
+    ctx: &serenity::Context,
+    event: &serenity::TypingStartEvent,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    if event.user_id == ctx.cache.current_user().id {
+        return Ok(());
+    }
+
+    if event.guild_id.is_none() {
+        let ticket_channel_id: Option<i64> = sqlx::query_scalar(
+            "SELECT channel_id FROM tickets WHERE user_id = ?"
+        )
+        .bind(event.user_id.get() as i64)
+        .fetch_optional(&data.db)
+        .await?;
+
+        if let Some(channel_id) = ticket_channel_id {
+            let _ = serenity::ChannelId::new(channel_id as u64).broadcast_typing(ctx).await;
+        }
+    } else {
+        if event.member.as_ref().is_some_and(|m| m.user.bot) {
+            return Ok(());
+        }
+
+        let ticket_user_id: Option<i64> = sqlx::query_scalar(
+            "SELECT user_id FROM tickets WHERE channel_id = ?"
+        )
+        .bind(event.channel_id.get() as i64)
+        .fetch_optional(&data.db)
+        .await?;
+
+        if let Some(uid) = ticket_user_id {
+            let user_id = serenity::UserId::new(uid as u64);
+            if let Ok(dm) = user_id.create_dm_channel(ctx).await {
+                let _ = dm.id.broadcast_typing(ctx).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn mark_answered(
+    http: &serenity::Http,
+    db: &sqlx::Pool<sqlx::Sqlite>,
+    user_id: u64,
+) -> Result<(), crate::Error> {
+    let pending: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT message_id, dm_channel_id FROM ticket_receipts WHERE user_id = ?"
+    )
+    .bind(user_id as i64)
+    .fetch_all(db)
+    .await?;
+
+    for (message_id, dm_channel_id) in pending {
+        let channel = serenity::ChannelId::new(dm_channel_id as u64);
+        let message = serenity::MessageId::new(message_id as u64);
+
+        let _ = channel.delete_reaction(http, message, None, serenity::ReactionType::Unicode(RECEIPT_PENDING.to_string())).await;
+        let _ = channel.create_reaction(http, message, serenity::ReactionType::Unicode(RECEIPT_ANSWERED.to_string())).await;
+    }
+
+    sqlx::query("DELETE FROM ticket_receipts WHERE user_id = ?")
+        .bind(user_id as i64)
+        .execute(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_ticket_records(
+    db: &sqlx::Pool<sqlx::Sqlite>,
+    user_id: i64,
+    channel_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM tickets WHERE user_id = ?")
+        .bind(user_id)
+        .execute(db)
+        .await?;
+
+    sqlx::query("DELETE FROM ticket_receipts WHERE user_id = ?")
+        .bind(user_id)
+        .execute(db)
+        .await?;
+
+    sqlx::query("DELETE FROM ticket_tags WHERE channel_id = ?")
+        .bind(channel_id)
+        .execute(db)
+        .await?;
+
+    sqlx::query("DELETE FROM ticket_reminders WHERE channel_id = ?")
+        .bind(channel_id)
+        .execute(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn check_ticket_reminders(db: &sqlx::Pool<sqlx::Sqlite>, http: &serenity::Http) {
+    let now = chrono::Utc::now().timestamp();
+
+    let due: Vec<(i64, String, i64, i64, i64, String)> = sqlx::query_as(
+        "SELECT id, kind, channel_id, user_id, staff_id, message FROM ticket_reminders WHERE due_at <= ?"
+    )
+    .bind(now)
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+
+    for (id, kind, channel_id, user_id, staff_id, message) in due {
+        let _ = sqlx::query("DELETE FROM ticket_reminders WHERE id = ?")
+            .bind(id)
+            .execute(db)
+            .await;
+
+        let still_open: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM tickets WHERE channel_id = ?)"
+        )
+        .bind(channel_id)
+        .fetch_one(db)
+        .await
+        .unwrap_or(false);
+
+        if !still_open {
+            continue;
+        }
+
+        let channel = serenity::ChannelId::new(channel_id as u64);
+
+        if kind == "followup" {
+            let content = format!("**Support**: Bonjour, nous revenons vers vous au sujet de votre ticket.\n{}", message);
+            let user = serenity::UserId::new(user_id as u64);
+            if let Ok(dm) = user.create_dm_channel(http).await {
+                let _ = dm.say(http, &content).await;
+            }
+
+            let _ = channel.say(http, format!("**Suivi automatique (<@{}>):** {}", staff_id, message)).await;
+            let _ = mark_answered(http, db, user_id as u64).await;
+        } else {
+            let _ = channel.say(http, format!("⏰ <@{}> Rappel : {}", staff_id, message)).await;
+        }
+    }
+}
+
 async fn handle_component(
     ctx: &serenity::Context,
     component: &serenity::ComponentInteraction,
@@ -162,12 +332,7 @@ async fn handle_component(
     } else if custom_id == "category_select" {
         if let serenity::ComponentInteractionDataKind::StringSelect { values } = &component.data.kind {
             if let Some(value) = values.first() {
-                let category = match value.as_str() {
-                    "cat_partnership" => TicketCategory::Partnership,
-                    "cat_recruitment" => TicketCategory::Recruitment,
-                    "cat_support" => TicketCategory::Support,
-                    _ => TicketCategory::Other,
-                };
+                let category = category_from_key(value);
 
                 let lang = if let Some(TicketState::ChoosingCategory { language }) = state {
                     language
@@ -184,7 +349,7 @@ async fn handle_component(
                 }
 
                 let text = if lang == "FR" { 
-                    format!("Vous avez choisi **{}**. Veuillez maintenant décrire votre demande en un seul message.", category.to_string())
+                    format!("Vous avez choisi **{}**. Veuillez maintenant décrire votre demande en un seul message.", category)
                 } else { 
                     format!("You chose **{:?}**. Please describe your request in a single message.", category)
                 };
@@ -202,14 +367,168 @@ async fn handle_component(
     Ok(())
 }
 
-async fn create_ticket(
+fn category_from_key(key: &str) -> TicketCategory {
+    match key {
+        "cat_partnership" => TicketCategory::Partnership,
+        "cat_recruitment" => TicketCategory::Recruitment,
+        "cat_support" => TicketCategory::Support,
+        _ => TicketCategory::Other,
+    }
+}
+
+fn confirmation_message(language: &str) -> &'static str {
+    if language == "FR" {
+        "Votre ticket a été créé avec succès ! Un membre du staff va vous répondre bientôt."
+    } else {
+        "Your ticket has been successfully created ! A staff member will answer you shortly."
+    }
+}
+
+async fn handle_panel_component(
+    ctx: &serenity::Context,
+    component: &serenity::ComponentInteraction,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let user_id = component.user.id.get();
+    let custom_id = component.data.custom_id.as_str();
+
+    if let Some(category_key) = custom_id.strip_prefix("panel_cat_") {
+        let reason: Option<String> = sqlx::query_scalar(
+            "SELECT reason FROM blacklist WHERE user_id = ?"
+        )
+        .bind(user_id as i64)
+        .fetch_optional(&data.db)
+        .await?;
+
+        if let Some(reason) = reason {
+            component.create_response(ctx, serenity::CreateInteractionResponse::Message(
+                serenity::CreateInteractionResponseMessage::new()
+                    .embed(serenity::CreateEmbed::new()
+                        .title("Accès refusé")
+                        .description(format!("Vous avez été blacklisté du système de ticket.\n**Raison:** {}", reason))
+                        .color(0xe74c3c))
+                    .ephemeral(true)
+            )).await?;
+            return Ok(());
+        }
+
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT channel_id FROM tickets WHERE user_id = ?"
+        )
+        .bind(user_id as i64)
+        .fetch_optional(&data.db)
+        .await?;
+
+        if let Some(channel_id) = existing {
+            component.create_response(ctx, serenity::CreateInteractionResponse::Message(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(format!("Vous avez déjà un ticket ouvert : <#{}>. Vous pouvez aussi nous écrire en message privé.", channel_id))
+                    .ephemeral(true)
+            )).await?;
+            return Ok(());
+        }
+
+        let buttons = vec![
+            serenity::CreateButton::new(format!("panel_lang_FR:cat_{}", category_key)).label("Français").style(serenity::ButtonStyle::Primary),
+            serenity::CreateButton::new(format!("panel_lang_EN:cat_{}", category_key)).label("English").style(serenity::ButtonStyle::Secondary),
+        ];
+
+        component.create_response(ctx, serenity::CreateInteractionResponse::Message(
+            serenity::CreateInteractionResponseMessage::new()
+                .embed(serenity::CreateEmbed::new()
+                    .description("Please select your language / Veuillez choisir votre langue")
+                    .color(0x5865F2))
+                .components(vec![serenity::CreateActionRow::Buttons(buttons)])
+                .ephemeral(true)
+        )).await?;
+    } else if let Some(rest) = custom_id.strip_prefix("panel_lang_") {
+        let Some((lang, category_key)) = rest.split_once(':') else {
+            return Ok(());
+        };
+
+        let (title, label) = if lang == "FR" {
+            ("Ouvrir un ticket", "Décrivez votre demande")
+        } else {
+            ("Open a ticket", "Describe your request")
+        };
+
+        let modal = serenity::CreateModal::new(format!("panel_modal_{}:{}", lang, category_key), title)
+            .components(vec![
+                serenity::CreateActionRow::InputText(serenity::CreateInputText::new(
+                    serenity::InputTextStyle::Paragraph, label, "message"
+                ).max_length(1024)),
+            ]);
+
+        component.create_response(ctx, serenity::CreateInteractionResponse::Modal(modal)).await?;
+    }
+
+    Ok(())
+}
+
+async fn handle_panel_modal(
+    ctx: &serenity::Context,
+    modal: &serenity::ModalInteraction,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let Some((lang, category_key)) = modal.data.custom_id
+        .strip_prefix("panel_modal_")
+        .and_then(|rest| rest.split_once(':'))
+    else {
+        return Ok(());
+    };
+
+    let mut content = String::new();
+    for row in &modal.data.components {
+        if let Some(serenity::ActionRowComponent::InputText(input)) = row.components.first() {
+            if input.custom_id == "message" {
+                content = input.value.clone().unwrap_or_default();
+            }
+        }
+    }
+
+    modal.defer_ephemeral(ctx).await?;
+
+    let already_open: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM tickets WHERE user_id = ?)"
+    )
+    .bind(modal.user.id.get() as i64)
+    .fetch_one(&data.db)
+    .await?;
+
+    if already_open {
+        modal.edit_response(ctx, serenity::EditInteractionResponse::new()
+            .content("Vous avez déjà un ticket ouvert.")
+        ).await?;
+        return Ok(());
+    }
+
+    let channel = create_ticket(ctx, data, &modal.user, &content, lang, category_from_key(category_key)).await?;
+
+    {
+        let mut states = data.ticket_states.write().await;
+        states.remove(&modal.user.id.get());
+    }
+
+    modal.edit_response(ctx, serenity::EditInteractionResponse::new()
+        .content(format!("{}\n<#{}>", confirmation_message(lang), channel.id))
+    ).await?;
+
+    if let Ok(dm) = modal.user.id.create_dm_channel(ctx).await {
+        let _ = dm.say(ctx, confirmation_message(lang)).await;
+    }
+
+    Ok(())
+}
+
+pub async fn create_ticket(
     ctx: &serenity::Context,
-    msg: &serenity::Message,
     data: &Data,
-    language: String,
+    user: &serenity::User,
+    content: &str,
+    language: &str,
     category: TicketCategory,
-) -> Result<(), crate::Error> {
-    let user_id = msg.author.id.get();
+) -> Result<serenity::GuildChannel, crate::Error> {
+    let user_id = user.id.get();
     let guild_id = std::env::var("DISCORD_GUILD_ID")?.parse::<u64>()?;
     let guild_id = serenity::GuildId::new(guild_id);
 
@@ -235,47 +554,52 @@ async fn create_ticket(
         TicketCategory::Other => data.config.categories.other,
     };
 
-    let channel_name = format!("{}-{}", msg.author.name, count);
+    let channel_name = format!("{}-{}", user.name, count);
     
-    let permissions = vec![
-        serenity::PermissionOverwrite {
-            allow: serenity::Permissions::empty(),
-            deny: serenity::Permissions::VIEW_CHANNEL,
-            kind: serenity::PermissionOverwriteType::Role(serenity::RoleId::new(guild_id.get())),
-        },
-        serenity::PermissionOverwrite {
-            allow: serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::SEND_MESSAGES | serenity::Permissions::READ_MESSAGE_HISTORY,
-            deny: serenity::Permissions::empty(),
-            kind: serenity::PermissionOverwriteType::Role(serenity::RoleId::new(data.config.roles.staff_role_id)),
-        },
-        serenity::PermissionOverwrite {
-            allow: serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::SEND_MESSAGES | serenity::Permissions::READ_MESSAGE_HISTORY | serenity::Permissions::MANAGE_CHANNELS,
-            deny: serenity::Permissions::empty(),
-            kind: serenity::PermissionOverwriteType::Member(ctx.cache.current_user().id),
-        },
-    ];
-
-    let builder = serenity::CreateChannel::new(channel_name)
-        .kind(serenity::ChannelType::Text)
-        .category(serenity::ChannelId::new(category_id))
-        .topic(format!("Ticket de {} | ID: {}", msg.author.name, user_id))
-        .permissions(permissions);
-
-    let channel = guild_id.create_channel(ctx, builder).await?;
+    let channel = match data.config.tickets.mode {
+        TicketMode::Channel => {
+            let permissions = vec![
+                serenity::PermissionOverwrite {
+                    allow: serenity::Permissions::empty(),
+                    deny: serenity::Permissions::VIEW_CHANNEL,
+                    kind: serenity::PermissionOverwriteType::Role(serenity::RoleId::new(guild_id.get())),
+                },
+                serenity::PermissionOverwrite {
+                    allow: serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::SEND_MESSAGES | serenity::Permissions::READ_MESSAGE_HISTORY,
+                    deny: serenity::Permissions::empty(),
+                    kind: serenity::PermissionOverwriteType::Role(serenity::RoleId::new(data.config.roles.staff_role_id)),
+                },
+                serenity::PermissionOverwrite {
+                    allow: serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::SEND_MESSAGES | serenity::Permissions::READ_MESSAGE_HISTORY | serenity::Permissions::MANAGE_CHANNELS,
+                    deny: serenity::Permissions::empty(),
+                    kind: serenity::PermissionOverwriteType::Member(ctx.cache.current_user().id),
+                },
+            ];
+
+            let builder = serenity::CreateChannel::new(channel_name)
+                .kind(serenity::ChannelType::Text)
+                .category(serenity::ChannelId::new(category_id))
+                .topic(format!("Ticket de {} | ID: {}", user.name, user_id))
+                .permissions(permissions);
+
+            guild_id.create_channel(ctx, builder).await?
+        }
+        TicketMode::Thread => create_ticket_thread(ctx, data, channel_name, user).await?,
+    };
 
     let embed = serenity::CreateEmbed::new()
         .title(format!("Nouveau Ticket #{}", count))
-        .field("Utilisateur", format!("<@{}> ({})", user_id, msg.author.name), true)
+        .field("Utilisateur", format!("<@{}> ({})", user_id, user.name), true)
         .field("Catégorie", category.to_string(), true)
-        .field("Langue", &language, true)
-        .field("Message Initial", &msg.content, false)
+        .field("Langue", language, true)
+        .field("Message Initial", content, false)
         .color(0xe67e22)
         .timestamp(serenity::Timestamp::now());
 
-    let content = format!("<@&{}>", data.config.roles.staff_role_id);
+    let ping = format!("<@&{}>", data.config.roles.staff_role_id);
 
     channel.send_message(ctx, serenity::CreateMessage::new()
-        .content(content)
+        .content(ping)
         .embed(embed)
     ).await?;
 
@@ -287,19 +611,35 @@ async fn create_ticket(
     .bind(channel.id.get() as i64)
     .bind(format!("{:?}", category))
     .bind(chrono::Utc::now().timestamp())
-    .bind(&msg.content)
+    .bind(content)
     .bind(chrono::Utc::now().timestamp())
     .execute(&data.db)
     .await?;
 
+    Ok(channel)
+}
 
-    let confirmation_message = if language == "FR" {
-        "Votre ticket a été créé avec succès ! Un membre du staff va vous répondre bientôt."
+async fn create_ticket_thread(
+    ctx: &serenity::Context,
+    data: &Data,
+    name: String,
+    user: &serenity::User,
+) -> Result<serenity::GuildChannel, crate::Error> {
+    let parent_id = serenity::ChannelId::new(data.config.tickets.thread_parent_id);
+    let parent = parent_id.to_channel(ctx).await?.guild().ok_or("Le salon parent des tickets n'est pas un salon de serveur")?;
+
+    let thread = if parent.kind == serenity::ChannelType::Forum {
+        parent_id.create_forum_post(ctx, serenity::CreateForumPost::new(
+            name,
+            serenity::CreateMessage::new().content(format!("Ticket de {} | ID: {}", user.name, user.id)),
+        ).auto_archive_duration(serenity::AutoArchiveDuration::OneWeek)).await?
     } else {
-        "Your ticket has been successfully created ! A staff member will answer you shortly."
+        parent_id.create_thread(ctx, serenity::CreateThread::new(name)
+            .kind(serenity::ChannelType::PrivateThread)
+            .invitable(false)
+            .auto_archive_duration(serenity::AutoArchiveDuration::OneWeek)
+        ).await?
     };
-    
-    msg.channel_id.say(ctx, confirmation_message).await?;
 
-    Ok(())
+    Ok(thread)
 }
@@ -2,22 +2,27 @@ use serde::{Deserialize, Serialize};
 use poise::serenity_prelude as serenity;
 use sqlx::FromRow;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, poise::ChoiceParameter)]
 pub enum TicketCategory {
+    #[name = "Partenariat"]
     Partnership,
+    #[name = "Recrutement"]
     Recruitment,
+    #[name = "Support"]
     Support,
+    #[name = "Autres"]
     Other,
 }
 
-impl TicketCategory {
-    pub fn to_string(&self) -> String {
-        match self {
-            TicketCategory::Partnership => "Partenariat".to_string(),
-            TicketCategory::Recruitment => "Recrutement".to_string(),
-            TicketCategory::Support => "Support".to_string(),
-            TicketCategory::Other => "Autres".to_string(),
-        }
+impl std::fmt::Display for TicketCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            TicketCategory::Partnership => "Partenariat",
+            TicketCategory::Recruitment => "Recrutement",
+            TicketCategory::Support => "Support",
+            TicketCategory::Other => "Autres",
+        };
+        f.write_str(label)
     }
 }
 
@@ -38,4 +43,6 @@ pub struct TicketInfo {
     pub initial_message: String,
     pub last_activity: i64,
     pub has_been_reminded: bool,
+    pub assigned_to: Option<i64>,
+    pub awaiting_staff: bool,
 }
@@ -0,0 +1,161 @@
+use poise::serenity_prelude as serenity;
+use sqlx::{Pool, Sqlite};
+use crate::Data;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Period {
+    #[name = "Dernières 24h"]
+    Day,
+    #[name = "7 derniers jours"]
+    Week,
+    #[name = "30 derniers jours"]
+    Month,
+    #[name = "Depuis toujours"]
+    All,
+}
+
+impl Period {
+    pub fn since(&self, now: i64) -> i64 {
+        match self {
+            Period::Day => now - 86400,
+            Period::Week => now - 7 * 86400,
+            Period::Month => now - 30 * 86400,
+            Period::All => 0,
+        }
+    }
+}
+
+fn afk_channel(ctx: &serenity::Context, guild_id: serenity::GuildId) -> Option<serenity::ChannelId> {
+    ctx.cache.guild(guild_id)?.afk_metadata.as_ref().map(|afk| afk.afk_channel_id)
+}
+
+async fn close_session(db: &Pool<Sqlite>, user_id: serenity::UserId, now: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE voice_sessions SET ended_at = ? WHERE user_id = ? AND ended_at IS NULL")
+        .bind(now)
+        .bind(user_id.get() as i64)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+async fn open_session(
+    db: &Pool<Sqlite>,
+    user_id: serenity::UserId,
+    channel_id: serenity::ChannelId,
+    muted: bool,
+    deafened: bool,
+    counted: bool,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO voice_sessions (user_id, channel_id, muted, deafened, counted, started_at, last_seen)
+         VALUES (?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(user_id.get() as i64)
+    .bind(channel_id.get() as i64)
+    .bind(muted)
+    .bind(deafened)
+    .bind(counted)
+    .bind(now)
+    .bind(now)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+pub async fn record_state(
+    ctx: &serenity::Context,
+    state: &serenity::VoiceState,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    if state.member.as_ref().is_some_and(|m| m.user.bot) {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    close_session(&data.db, state.user_id, now).await?;
+
+    if let (Some(channel_id), Some(guild_id)) = (state.channel_id, state.guild_id) {
+        let counted = !state.self_deaf && afk_channel(ctx, guild_id) != Some(channel_id);
+        open_session(&data.db, state.user_id, channel_id, state.self_mute, state.self_deaf, counted, now).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn resume_sessions(ctx: &serenity::Context, data: &Data) -> Result<(), crate::Error> {
+    sqlx::query("UPDATE voice_sessions SET ended_at = last_seen WHERE ended_at IS NULL")
+        .execute(&data.db)
+        .await?;
+
+    let now = chrono::Utc::now().timestamp();
+
+    for guild_id in ctx.cache.guilds() {
+        let states: Vec<(serenity::UserId, serenity::ChannelId, bool, bool, bool)> = {
+            let Some(guild) = ctx.cache.guild(guild_id) else { continue };
+            let afk = guild.afk_metadata.as_ref().map(|afk| afk.afk_channel_id);
+
+            guild.voice_states.values()
+                .filter(|s| !guild.members.get(&s.user_id).is_some_and(|m| m.user.bot))
+                .filter_map(|s| s.channel_id.map(|c| (s.user_id, c, s.self_mute, s.self_deaf, !s.self_deaf && afk != Some(c))))
+                .collect()
+        };
+
+        for (user_id, channel_id, muted, deafened, counted) in states {
+            open_session(&data.db, user_id, channel_id, muted, deafened, counted, now).await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn checkpoint(db: &Pool<Sqlite>) {
+    let _ = sqlx::query("UPDATE voice_sessions SET last_seen = ? WHERE ended_at IS NULL")
+        .bind(chrono::Utc::now().timestamp())
+        .execute(db)
+        .await;
+}
+
+pub async fn user_total(db: &Pool<Sqlite>, user_id: serenity::UserId, period: Period) -> Result<i64, sqlx::Error> {
+    let now = chrono::Utc::now().timestamp();
+
+    let total: Option<i64> = sqlx::query_scalar(
+        "SELECT SUM(MAX(0, COALESCE(ended_at, ?) - MAX(started_at, ?)))
+         FROM voice_sessions WHERE user_id = ? AND counted = 1"
+    )
+    .bind(now)
+    .bind(period.since(now))
+    .bind(user_id.get() as i64)
+    .fetch_one(db)
+    .await?;
+
+    Ok(total.unwrap_or(0))
+}
+
+pub async fn leaderboard(db: &Pool<Sqlite>, period: Period, limit: i64) -> Result<Vec<(i64, i64)>, sqlx::Error> {
+    let now = chrono::Utc::now().timestamp();
+
+    sqlx::query_as(
+        "SELECT user_id, SUM(MAX(0, COALESCE(ended_at, ?) - MAX(started_at, ?))) AS total
+         FROM voice_sessions WHERE counted = 1
+         GROUP BY user_id HAVING total > 0
+         ORDER BY total DESC LIMIT ?"
+    )
+    .bind(now)
+    .bind(period.since(now))
+    .bind(limit)
+    .fetch_all(db)
+    .await
+}
+
+pub fn format_duration(seconds: i64) -> String {
+    let days = seconds / 86400;
+    let hours = (seconds % 86400) / 3600;
+    let minutes = (seconds % 3600) / 60;
+
+    if days > 0 {
+        format!("{}j {}h {}m", days, hours, minutes)
+    } else {
+        format!("{}h {}m", hours, minutes)
+    }
+}
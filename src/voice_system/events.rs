@@ -1,5 +1,10 @@
 use poise::serenity_prelude as serenity;
 use crate::Data;
+use crate::voice_system::{activity, panel, preferences, waiting};
+use crate::config::{VoiceHub, VoicePreset, VoiceRoomKind};
+use std::time::Duration;
+
+const HUB_JOIN_DEBOUNCE: Duration = Duration::from_secs(3);
 
 pub async fn handle_event(
     ctx: &serenity::Context,
@@ -11,19 +16,125 @@ pub async fn handle_event(
         serenity::FullEvent::VoiceStateUpdate { old, new } => {
             handle_voice_update(ctx, old.as_ref(), new, data).await?;
         }
+        serenity::FullEvent::CacheReady { .. } => {
+            if let Err(why) = activity::resume_sessions(ctx, data).await {
+                eprintln!("Erreur lors de la reprise des sessions vocales : {:?}", why);
+            }
+            reconcile_voice_channels(ctx, data).await;
+        }
         serenity::FullEvent::ChannelDelete { channel, .. } => {
             handle_channel_delete(ctx, channel, data).await?;
         }
+        serenity::FullEvent::InteractionCreate { interaction } => {
+            match interaction {
+                serenity::Interaction::Component(component) if component.data.custom_id.starts_with("voice_panel_") => {
+                    panel::handle_component(ctx, component, data).await?;
+                }
+                serenity::Interaction::Modal(modal) if modal.data.custom_id.starts_with("voice_panel_modal_") => {
+                    panel::handle_modal(ctx, modal, data).await?;
+                }
+                _ => {}
+            }
+        }
         _ => {}
     }
     Ok(())
 }
 
+pub async fn reconcile_voice_channels(ctx: &serenity::Context, data: &Data) {
+    let tracked: Vec<(i64, i64)> = sqlx::query_as("SELECT channel_id, owner_id FROM voice_channels")
+        .fetch_all(&data.db)
+        .await
+        .unwrap_or_default();
+
+    for (channel_id, owner_id) in tracked {
+        if let Err(why) = reconcile_voice_channel(ctx, serenity::ChannelId::new(channel_id as u64), owner_id, data).await {
+            eprintln!("Erreur lors de la réconciliation du salon vocal {} : {:?}", channel_id, why);
+        }
+    }
+}
+
+async fn fetch_guild_channel(
+    ctx: &serenity::Context,
+    channel_id: serenity::ChannelId,
+) -> Result<Option<serenity::GuildChannel>, crate::Error> {
+    match channel_id.to_channel(ctx).await {
+        Ok(channel) => Ok(channel.guild()),
+        Err(serenity::Error::Http(e)) if e.status_code() == Some(serenity::http::StatusCode::NOT_FOUND) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn reconcile_voice_channel(
+    ctx: &serenity::Context,
+    channel_id: serenity::ChannelId,
+    owner_id: i64,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let _guard = data.voice_locks.lock(channel_id.get()).await;
+
+    let is_managed: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM voice_channels WHERE channel_id = ?)"
+    )
+    .bind(channel_id.get() as i64)
+    .fetch_one(&data.db)
+    .await?;
+
+    if !is_managed {
+        return Ok(());
+    }
+
+    let Some(channel) = fetch_guild_channel(ctx, channel_id).await? else {
+        forget_voice_channel(ctx, channel_id, data).await?;
+        println!("Salon vocal {} introuvable, entrée supprimée de la base.", channel_id);
+        return Ok(());
+    };
+
+    let members = channel.members(ctx)?;
+    let human_members: Vec<_> = members.into_iter().filter(|m| !m.user.bot).collect();
+
+    let present: Vec<i64> = human_members.iter().map(|m| m.user.id.get() as i64).collect();
+    let recorded: Vec<i64> = sqlx::query_scalar("SELECT user_id FROM voice_presence WHERE channel_id = ?")
+        .bind(channel_id.get() as i64)
+        .fetch_all(&data.db)
+        .await?;
+
+    for user_id in recorded.into_iter().filter(|id| !present.contains(id)) {
+        sqlx::query("DELETE FROM voice_presence WHERE channel_id = ? AND user_id = ?")
+            .bind(channel_id.get() as i64)
+            .bind(user_id)
+            .execute(&data.db)
+            .await?;
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    for member in &human_members {
+        sqlx::query("INSERT OR IGNORE INTO voice_presence (channel_id, user_id, joined_at) VALUES (?, ?, ?)")
+            .bind(channel_id.get() as i64)
+            .bind(member.user.id.get() as i64)
+            .bind(now)
+            .execute(&data.db)
+            .await?;
+    }
+
+    if human_members.is_empty() {
+        delete_voice_channel(ctx, channel_id, data).await?;
+    } else if !human_members.iter().any(|m| m.user.id.get() as i64 == owner_id) {
+        if let Some(successor) = pick_successor(channel_id, &human_members, data).await? {
+            transfer_ownership(ctx, channel_id, successor, data).await?;
+        }
+    }
+
+    Ok(())
+}
+
 async fn handle_channel_delete(
     ctx: &serenity::Context,
     channel: &serenity::GuildChannel,
     data: &Data,
 ) -> Result<(), crate::Error> {
+    let _guard = data.voice_locks.lock(channel.id.get()).await;
+
     let is_managed: bool = sqlx::query_scalar(
         "SELECT EXISTS(SELECT 1 FROM voice_channels WHERE channel_id = ?)"
     )
@@ -31,6 +142,11 @@ async fn handle_channel_delete(
     .fetch_one(&data.db)
     .await?;
 
+    sqlx::query("UPDATE voice_channels SET waiting_channel_id = NULL WHERE waiting_channel_id = ?")
+        .bind(channel.id.get() as i64)
+        .execute(&data.db)
+        .await?;
+
     if is_managed {
         let owner_id: Option<i64> = sqlx::query_scalar(
             "SELECT owner_id FROM voice_channels WHERE channel_id = ?"
@@ -39,10 +155,7 @@ async fn handle_channel_delete(
         .fetch_optional(&data.db)
         .await?;
 
-        sqlx::query("DELETE FROM voice_channels WHERE channel_id = ?")
-            .bind(channel.id.get() as i64)
-            .execute(&data.db)
-            .await?;
+        forget_voice_channel(ctx, channel.id, data).await?;
 
         if let Some(uid) = owner_id {
             let log_channel = serenity::ChannelId::new(data.config.channels.voice_log_channel_id);
@@ -66,46 +179,95 @@ async fn handle_voice_update(
     new: &serenity::VoiceState,
     data: &Data,
 ) -> Result<(), crate::Error> {
+    let _user_guard = data.voice_locks.lock(new.user_id.get()).await;
+
+    activity::record_state(ctx, new, data).await?;
+
     if let Some(channel_id) = new.channel_id {
-        if data.config.channels.jtc_channel_ids.contains(&channel_id.get()) {
-            create_voice_channel(ctx, new, data).await?;
+        if let Some(hub) = data.config.voice_hubs.iter().find(|h| h.channel_id == channel_id.get()) {
+            if data.voice_locks.accept_hub_join(new.user_id.get(), HUB_JOIN_DEBOUNCE) {
+                if let Err(why) = create_voice_channel(ctx, new, hub, data).await {
+                    eprintln!("Erreur lors de la création du salon vocal pour {} : {:?}", new.user_id, why);
+                }
+            } else {
+                defer_hub_join(ctx, new, hub, data);
+            }
+        }
+
+        let moved = old.and_then(|o| o.channel_id) != Some(channel_id);
+        let is_bot = new.member.as_ref().is_some_and(|m| m.user.bot);
+        if moved && !is_bot {
+            if let Some((room_id, owner_id)) = waiting::room_for_waiting_channel(&data.db, channel_id).await? {
+                if let (true, Some(guild_id)) = (new.user_id == owner_id, new.guild_id) {
+                    guild_id.move_member(ctx, owner_id, room_id).await?;
+                } else {
+                    waiting::prompt_owner(ctx, room_id, owner_id, new.user_id).await?;
+                }
+            }
+        }
+    }
+
+    if let Some(old_channel_id) = old.and_then(|o| o.channel_id) {
+        if new.channel_id != Some(old_channel_id) {
+            waiting::revoke_temporary_permit(ctx, old_channel_id, new.user_id, data).await?;
+        }
+    }
+
+    let left_channel = old.and_then(|o| o.channel_id).filter(|c| new.channel_id != Some(*c));
+
+    if let Some(channel_id) = new.channel_id.filter(|c| old.and_then(|o| o.channel_id) != Some(*c)) {
+        let _channel_guard = data.voice_locks.lock(channel_id.get()).await;
+
+        if panel::channel_owner(&data.db, channel_id).await?.is_some() {
+            sqlx::query("INSERT OR REPLACE INTO voice_presence (channel_id, user_id, joined_at) VALUES (?, ?, ?)")
+                .bind(channel_id.get() as i64)
+                .bind(new.user_id.get() as i64)
+                .bind(chrono::Utc::now().timestamp())
+                .execute(&data.db)
+                .await?;
+
+            try_reclaim(ctx, channel_id, new.user_id, data).await?;
         }
     }
 
-    if let Some(old_state) = old {
-        if let Some(channel_id) = old_state.channel_id {
-            let is_temp_channel: bool = sqlx::query_scalar(
-                "SELECT EXISTS(SELECT 1 FROM voice_channels WHERE channel_id = ?)"
-            )
+    if let Some(channel_id) = left_channel {
+        let _channel_guard = data.voice_locks.lock(channel_id.get()).await;
+
+        sqlx::query("DELETE FROM voice_presence WHERE channel_id = ? AND user_id = ?")
             .bind(channel_id.get() as i64)
-            .fetch_one(&data.db)
+            .bind(new.user_id.get() as i64)
+            .execute(&data.db)
             .await?;
 
-            if is_temp_channel {
-                let channel = channel_id.to_channel(ctx).await?.guild().unwrap();
-                let members = channel.members(ctx)?;
-                
-                let human_members: Vec<_> = members.iter().filter(|m| !m.user.bot).collect();
+        let is_temp_channel: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM voice_channels WHERE channel_id = ?)"
+        )
+        .bind(channel_id.get() as i64)
+        .fetch_one(&data.db)
+        .await?;
 
-                if human_members.is_empty() {
-                    delete_voice_channel(ctx, channel_id, data).await?;
-                } else {
-                    let user_id = old_state.user_id;
-                    
-                    let owner_id: Option<i64> = sqlx::query_scalar(
-                        "SELECT owner_id FROM voice_channels WHERE channel_id = ?"
-                    )
-                    .bind(channel_id.get() as i64)
-                    .fetch_optional(&data.db)
-                    .await?;
-
-                    if let Some(owner_id) = owner_id {
-                        if user_id.get() as i64 == owner_id {
-                            if let Some(new_owner) = human_members.first() {
-                                transfer_ownership(ctx, channel_id, new_owner.user.id, data).await?;
-                            }
-                        }
-                    }
+        if is_temp_channel {
+            let Some(channel) = fetch_guild_channel(ctx, channel_id).await? else {
+                forget_voice_channel(ctx, channel_id, data).await?;
+                println!("Salon vocal {} introuvable, entrée supprimée de la base.", channel_id);
+                return Ok(());
+            };
+            let members = channel.members(ctx)?;
+            
+            let human_members: Vec<_> = members.into_iter().filter(|m| !m.user.bot).collect();
+
+            if human_members.is_empty() {
+                delete_voice_channel(ctx, channel_id, data).await?;
+            } else if panel::channel_owner(&data.db, channel_id).await? == Some(new.user_id) {
+                if let Some(successor) = pick_successor(channel_id, &human_members, data).await? {
+                    transfer_ownership(ctx, channel_id, successor, data).await?;
+
+                    sqlx::query("UPDATE voice_channels SET previous_owner_id = ?, owner_left_at = ? WHERE channel_id = ?")
+                        .bind(new.user_id.get() as i64)
+                        .bind(chrono::Utc::now().timestamp())
+                        .bind(channel_id.get() as i64)
+                        .execute(&data.db)
+                        .await?;
                 }
             }
         }
@@ -114,24 +276,160 @@ async fn handle_voice_update(
     Ok(())
 }
 
+async fn pick_successor(
+    channel_id: serenity::ChannelId,
+    candidates: &[serenity::Member],
+    data: &Data,
+) -> Result<Option<serenity::UserId>, crate::Error> {
+    let presence: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT user_id, joined_at FROM voice_presence WHERE channel_id = ?"
+    )
+    .bind(channel_id.get() as i64)
+    .fetch_all(&data.db)
+    .await?;
+
+    let priority = &data.config.voice.successor_role_priority;
+
+    let successor = candidates.iter()
+        .map(|member| {
+            let rank = priority.iter()
+                .position(|role_id| member.roles.contains(&serenity::RoleId::new(*role_id)))
+                .unwrap_or(usize::MAX);
+            let joined_at = presence.iter()
+                .find(|(user_id, _)| *user_id == member.user.id.get() as i64)
+                .map(|(_, joined_at)| *joined_at)
+                .unwrap_or(i64::MAX);
+            (rank, joined_at, member.user.id)
+        })
+        .min()
+        .map(|(_, _, user_id)| user_id);
+
+    Ok(successor)
+}
+
+async fn try_reclaim(
+    ctx: &serenity::Context,
+    channel_id: serenity::ChannelId,
+    user_id: serenity::UserId,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let previous: Option<(Option<i64>, Option<i64>)> = sqlx::query_as(
+        "SELECT previous_owner_id, owner_left_at FROM voice_channels WHERE channel_id = ?"
+    )
+    .bind(channel_id.get() as i64)
+    .fetch_optional(&data.db)
+    .await?;
+
+    let Some((Some(previous_owner_id), Some(left_at))) = previous else {
+        return Ok(());
+    };
+
+    if previous_owner_id != user_id.get() as i64 {
+        return Ok(());
+    }
+
+    if chrono::Utc::now().timestamp() - left_at <= data.config.voice.reclaim_grace_secs {
+        transfer_ownership(ctx, channel_id, user_id, data).await?;
+    } else {
+        sqlx::query("UPDATE voice_channels SET previous_owner_id = NULL, owner_left_at = NULL WHERE channel_id = ?")
+            .bind(channel_id.get() as i64)
+            .execute(&data.db)
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn defer_hub_join(ctx: &serenity::Context, state: &serenity::VoiceState, hub: &VoiceHub, data: &Data) {
+    let (ctx, state, hub, data) = (ctx.clone(), state.clone(), hub.clone(), data.clone());
+
+    tokio::spawn(async move {
+        tokio::time::sleep(HUB_JOIN_DEBOUNCE).await;
+
+        let _user_guard = data.voice_locks.lock(state.user_id.get()).await;
+
+        let Some(guild_id) = state.guild_id else { return };
+        let still_in_hub = panel::member_voice_channel(&ctx, guild_id, state.user_id) == Some(serenity::ChannelId::new(hub.channel_id));
+
+        if still_in_hub && data.voice_locks.accept_hub_join(state.user_id.get(), HUB_JOIN_DEBOUNCE) {
+            if let Err(why) = create_voice_channel(&ctx, &state, &hub, &data).await {
+                eprintln!("Erreur lors de la création du salon vocal pour {} : {:?}", state.user_id, why);
+            }
+        }
+    });
+}
+
 async fn create_voice_channel(
     ctx: &serenity::Context,
     state: &serenity::VoiceState,
+    hub: &VoiceHub,
     data: &Data,
 ) -> Result<(), crate::Error> {
-    let guild_id = state.guild_id.unwrap();
-    let user = state.member.as_ref().unwrap().user.clone();
-    
-    let channel_name = format!("🔉〢{}", user.name);
+    let Some(guild_id) = state.guild_id else {
+        return Err("Mise à jour vocale reçue sans serveur".into());
+    };
+    let Some(user) = state.member.as_ref().map(|m| m.user.clone()) else {
+        return Err("Mise à jour vocale reçue sans membre".into());
+    };
+
+    let owned: Option<i64> = sqlx::query_scalar("SELECT channel_id FROM voice_channels WHERE owner_id = ? LIMIT 1")
+        .bind(user.id.get() as i64)
+        .fetch_optional(&data.db)
+        .await?;
+
+    if let Some(existing) = owned.map(|id| serenity::ChannelId::new(id as u64)) {
+        let _existing_guard = data.voice_locks.lock(existing.get()).await;
+
+        if fetch_guild_channel(ctx, existing).await?.is_some() {
+            guild_id.move_member(ctx, user.id, existing).await?;
+            return Ok(());
+        }
+
+        forget_voice_channel(ctx, existing, data).await?;
+    }
+
+    let _hub_guard = data.voice_locks.lock(hub.channel_id).await;
+
+    let (prefs, members) = if hub.allow_preferences {
+        (preferences::load(&data.db, user.id).await?, preferences::load_members(&data.db, user.id).await?)
+    } else {
+        (preferences::VoicePreferences::default(), Vec::new())
+    };
+
+    let used_numbers: Vec<i64> = sqlx::query_scalar(
+        "SELECT number FROM voice_channels WHERE hub_id = ? AND number IS NOT NULL"
+    )
+    .bind(hub.channel_id as i64)
+    .fetch_all(&data.db)
+    .await?;
+    let number = (1..).find(|n| !used_numbers.contains(n)).unwrap_or(1);
+
+    let template = prefs.name_template.as_deref().unwrap_or(&hub.name_template);
+    let channel_name = preferences::render_name(template, &user, number);
+
+    let mut voice_access = serenity::Permissions::CONNECT | serenity::Permissions::SPEAK | serenity::Permissions::USE_VAD;
+    if hub.kind == VoiceRoomKind::Stage {
+        voice_access.insert(serenity::Permissions::REQUEST_TO_SPEAK);
+    }
+    let (mut everyone_allow, mut everyone_deny) = match hub.preset {
+        VoicePreset::Public => (voice_access, serenity::Permissions::empty()),
+        VoicePreset::Locked => (serenity::Permissions::SPEAK | serenity::Permissions::USE_VAD, serenity::Permissions::CONNECT),
+        VoicePreset::Hidden | VoicePreset::Staff => (serenity::Permissions::empty(), serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::CONNECT),
+    };
+
+    if prefs.locked {
+        everyone_allow.remove(serenity::Permissions::CONNECT);
+        everyone_deny.insert(serenity::Permissions::CONNECT);
+    }
 
-    let permissions = vec![
+    let mut permissions = vec![
         serenity::PermissionOverwrite {
-            allow: serenity::Permissions::CONNECT | serenity::Permissions::SPEAK | serenity::Permissions::USE_VAD,
-            deny: serenity::Permissions::empty(),
+            allow: everyone_allow,
+            deny: everyone_deny,
             kind: serenity::PermissionOverwriteType::Role(serenity::RoleId::new(guild_id.get())),
         },
         serenity::PermissionOverwrite {
-            allow: serenity::Permissions::MANAGE_CHANNELS | serenity::Permissions::MUTE_MEMBERS | serenity::Permissions::DEAFEN_MEMBERS | serenity::Permissions::MOVE_MEMBERS,
+            allow: panel::OWNER_PERMISSIONS,
             deny: serenity::Permissions::empty(),
             kind: serenity::PermissionOverwriteType::Member(user.id),
         },
@@ -142,21 +440,77 @@ async fn create_voice_channel(
         },
     ];
 
-    let builder = serenity::CreateChannel::new(channel_name)
-        .kind(serenity::ChannelType::Voice)
-        .category(serenity::ChannelId::new(data.config.categories.voice_category_id))
+    if hub.preset == VoicePreset::Staff {
+        permissions.push(serenity::PermissionOverwrite {
+            allow: serenity::Permissions::VIEW_CHANNEL | voice_access,
+            deny: serenity::Permissions::empty(),
+            kind: serenity::PermissionOverwriteType::Role(serenity::RoleId::new(data.config.roles.staff_role_id)),
+        });
+    }
+
+    for (member_id, allowed) in members {
+        let access = serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::CONNECT;
+        let (allow, deny) = if allowed { (access, serenity::Permissions::empty()) } else { (serenity::Permissions::empty(), access) };
+        permissions.push(serenity::PermissionOverwrite {
+            allow,
+            deny,
+            kind: serenity::PermissionOverwriteType::Member(member_id),
+        });
+    }
+
+    let channel_kind = match hub.kind {
+        VoiceRoomKind::Voice => serenity::ChannelType::Voice,
+        VoiceRoomKind::Stage => serenity::ChannelType::Stage,
+    };
+
+    let mut builder = serenity::CreateChannel::new(channel_name)
+        .kind(channel_kind)
+        .category(serenity::ChannelId::new(hub.category_id.unwrap_or(data.config.categories.voice_category_id)))
         .permissions(permissions);
 
+    if let Some(limit) = prefs.user_limit.or(hub.user_limit.map(i64::from)) {
+        builder = builder.user_limit(limit.clamp(0, 99) as u32);
+    }
+    if let Some(kbps) = prefs.bitrate.or(hub.bitrate.map(i64::from)) {
+        let max = panel::max_bitrate_kbps(ctx, guild_id);
+        builder = builder.bitrate(kbps.clamp(8, max as i64) as u32 * 1000);
+    }
+
     let channel = guild_id.create_channel(ctx, builder).await?;
 
-    guild_id.edit_member(ctx, user.id, serenity::EditMember::new().voice_channel(channel.id)).await?;
+    if let Err(why) = guild_id.edit_member(ctx, user.id, serenity::EditMember::new().voice_channel(channel.id)).await {
+        let _ = channel.id.delete(ctx).await;
+        return Err(why.into());
+    }
 
-    sqlx::query("INSERT INTO voice_channels (channel_id, owner_id) VALUES (?, ?)")
+    sqlx::query("INSERT INTO voice_channels (channel_id, owner_id, hub_id, number) VALUES (?, ?, ?, ?)")
         .bind(channel.id.get() as i64)
         .bind(user.id.get() as i64)
+        .bind(hub.channel_id as i64)
+        .bind(number)
         .execute(&data.db)
         .await?;
 
+    sqlx::query("INSERT OR REPLACE INTO voice_presence (channel_id, user_id, joined_at) VALUES (?, ?, ?)")
+        .bind(channel.id.get() as i64)
+        .bind(user.id.get() as i64)
+        .bind(chrono::Utc::now().timestamp())
+        .execute(&data.db)
+        .await?;
+
+    let _ = channel.id.send_message(ctx, panel::panel_message()).await;
+
+    if let Some(welcome) = &hub.welcome_message {
+        let embed = serenity::CreateEmbed::new()
+            .title("Bienvenue")
+            .description(welcome.replace("{owner}", &format!("<@{}>", user.id)))
+            .color(0x3498db);
+
+        if let Ok(message) = channel.id.send_message(ctx, serenity::CreateMessage::new().embed(embed)).await {
+            let _ = message.pin(ctx).await;
+        }
+    }
+
     let log_channel = serenity::ChannelId::new(data.config.channels.voice_log_channel_id);
     let embed = serenity::CreateEmbed::new()
         .title("Vocal Créé")
@@ -169,24 +523,63 @@ async fn create_voice_channel(
     Ok(())
 }
 
+async fn forget_voice_channel(
+    ctx: &serenity::Context,
+    channel_id: serenity::ChannelId,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    waiting::cleanup_room(ctx, channel_id, data).await?;
+
+    sqlx::query("DELETE FROM voice_presence WHERE channel_id = ?")
+        .bind(channel_id.get() as i64)
+        .execute(&data.db)
+        .await?;
+
+    sqlx::query("DELETE FROM voice_channels WHERE channel_id = ?")
+        .bind(channel_id.get() as i64)
+        .execute(&data.db)
+        .await?;
+
+    Ok(())
+}
+
 async fn delete_voice_channel(
     ctx: &serenity::Context,
     channel_id: serenity::ChannelId,
     data: &Data,
 ) -> Result<(), crate::Error> {
-    let owner_id: Option<i64> = sqlx::query_scalar(
-        "SELECT owner_id FROM voice_channels WHERE channel_id = ?"
+    let row: Option<(i64, Option<i64>)> = sqlx::query_as(
+        "SELECT owner_id, hub_id FROM voice_channels WHERE channel_id = ?"
     )
     .bind(channel_id.get() as i64)
     .fetch_optional(&data.db)
     .await?;
+    let owner_id = row.map(|(owner_id, _)| owner_id);
 
-    sqlx::query("DELETE FROM voice_channels WHERE channel_id = ?")
-        .bind(channel_id.get() as i64)
-        .execute(&data.db)
-        .await?;
+    let export_chat = row
+        .and_then(|(_, hub_id)| hub_id)
+        .and_then(|hub_id| data.config.voice_hubs.iter().find(|h| h.channel_id == hub_id as u64))
+        .is_some_and(|hub| hub.export_chat);
 
-    channel_id.delete(ctx).await?;
+    let transcript = if export_chat {
+        match chat_transcript(ctx, channel_id).await {
+            Ok(transcript) => transcript,
+            Err(why) => {
+                eprintln!("Erreur lors de l'export du salon vocal {} : {:?}", channel_id, why);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    forget_voice_channel(ctx, channel_id, data).await?;
+
+    match channel_id.delete(ctx).await {
+        Ok(_) => {}
+        Err(serenity::Error::Http(e)) if e.status_code() == Some(serenity::http::StatusCode::NOT_FOUND) => return Ok(()),
+        Err(e) => return Err(e.into()),
+    }
 
     if let Some(uid) = owner_id {
         let log_channel = serenity::ChannelId::new(data.config.channels.voice_log_channel_id);
@@ -195,33 +588,92 @@ async fn delete_voice_channel(
             .description(format!("**Ancien Propriétaire :** <@{}>\n**Salon ID :** {}", uid, channel_id))
             .color(0xe74c3c)
             .timestamp(serenity::Timestamp::now());
-        
-        log_channel.send_message(ctx, serenity::CreateMessage::new().embed(embed)).await?;
+
+        let mut message = serenity::CreateMessage::new().embed(embed);
+        if let Some(transcript) = transcript {
+            message = message.add_file(serenity::CreateAttachment::bytes(transcript.into_bytes(), format!("vocal-{}.txt", channel_id)));
+        }
+
+        log_channel.send_message(ctx, message).await?;
     }
 
     Ok(())
 }
 
-async fn transfer_ownership(
+async fn chat_transcript(
+    ctx: &serenity::Context,
+    channel_id: serenity::ChannelId,
+) -> Result<Option<String>, crate::Error> {
+    let mut messages = Vec::new();
+    let mut before = None;
+
+    loop {
+        let mut request = serenity::GetMessages::new().limit(100);
+        if let Some(message_id) = before {
+            request = request.before(message_id);
+        }
+
+        let batch = channel_id.messages(ctx, request).await?;
+        let done = batch.len() < 100 || messages.len() >= 1000;
+        before = batch.last().map(|m| m.id);
+        messages.extend(batch);
+
+        if done {
+            break;
+        }
+    }
+
+    messages.retain(|m| !m.author.bot && !m.content.is_empty());
+    if messages.is_empty() {
+        return Ok(None);
+    }
+    messages.reverse();
+
+    let mut transcript = String::new();
+    transcript.push_str("=== TRANSCRIPT VOCAL ===\n");
+    transcript.push_str(&format!("Salon ID : {}\n", channel_id));
+    transcript.push_str(&format!("Supprimé le : {}\n", chrono::Local::now().format("%d/%m/%Y %H:%M:%S")));
+    transcript.push_str("========================\n\n");
+
+    for msg in &messages {
+        let time = msg.timestamp.format("%d/%m %H:%M:%S");
+        transcript.push_str(&format!("[{}] {}: {}\n", time, msg.author.name, msg.content));
+    }
+
+    Ok(Some(transcript))
+}
+
+pub async fn transfer_ownership(
     ctx: &serenity::Context,
     channel_id: serenity::ChannelId,
     new_owner_id: serenity::UserId,
     data: &Data,
 ) -> Result<(), crate::Error> {
+    let previous_owner = panel::channel_owner(&data.db, channel_id).await?;
+
+    if let Some(previous_owner) = previous_owner.filter(|id| *id != new_owner_id) {
+        channel_id.create_permission(ctx, serenity::PermissionOverwrite {
+            allow: serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::CONNECT,
+            deny: serenity::Permissions::empty(),
+            kind: serenity::PermissionOverwriteType::Member(previous_owner),
+        }).await?;
+    }
     
     let permissions = serenity::PermissionOverwrite {
-        allow: serenity::Permissions::MANAGE_CHANNELS | serenity::Permissions::MUTE_MEMBERS | serenity::Permissions::DEAFEN_MEMBERS | serenity::Permissions::MOVE_MEMBERS,
+        allow: panel::OWNER_PERMISSIONS,
         deny: serenity::Permissions::empty(),
         kind: serenity::PermissionOverwriteType::Member(new_owner_id),
     };
 
     channel_id.create_permission(ctx, permissions).await?;
     
-    sqlx::query("UPDATE voice_channels SET owner_id = ? WHERE channel_id = ?")
+    sqlx::query("UPDATE voice_channels SET owner_id = ?, previous_owner_id = NULL, owner_left_at = NULL WHERE channel_id = ?")
         .bind(new_owner_id.get() as i64)
         .bind(channel_id.get() as i64)
         .execute(&data.db)
         .await?;
+
+    let _ = channel_id.say(ctx, format!("👑 <@{}>, vous êtes maintenant le propriétaire de ce salon. Utilisez le panneau ci-dessus ou `/voice` pour le gérer.", new_owner_id)).await;
     
     let log_channel = serenity::ChannelId::new(data.config.channels.voice_log_channel_id);
     let embed = serenity::CreateEmbed::new()
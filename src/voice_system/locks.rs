@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::OwnedMutexGuard;
+
+#[derive(Default)]
+pub struct VoiceLocks {
+    locks: Mutex<HashMap<u64, Arc<tokio::sync::Mutex<()>>>>,
+    hub_joins: Mutex<HashMap<u64, Instant>>,
+}
+
+impl VoiceLocks {
+    pub async fn lock(&self, id: u64) -> OwnedMutexGuard<()> {
+        let mutex = {
+            let mut locks = self.locks.lock().unwrap();
+            locks.retain(|_, m| Arc::strong_count(m) > 1);
+            locks.entry(id).or_default().clone()
+        };
+        mutex.lock_owned().await
+    }
+
+    pub fn accept_hub_join(&self, user_id: u64, window: Duration) -> bool {
+        let mut joins = self.hub_joins.lock().unwrap();
+        let now = Instant::now();
+        joins.retain(|_, joined_at| now.duration_since(*joined_at) < window);
+
+        if joins.contains_key(&user_id) {
+            return false;
+        }
+
+        joins.insert(user_id, now);
+        true
+    }
+}
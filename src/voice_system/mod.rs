@@ -1 +1,6 @@
+pub mod activity;
 pub mod events;
+pub mod locks;
+pub mod panel;
+pub mod preferences;
+pub mod waiting;
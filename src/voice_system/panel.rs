@@ -0,0 +1,371 @@
+use poise::serenity_prelude as serenity;
+use crate::Data;
+use crate::voice_system::{preferences, waiting};
+
+pub const OWNER_PERMISSIONS: serenity::Permissions = serenity::Permissions::MANAGE_CHANNELS
+    .union(serenity::Permissions::MUTE_MEMBERS)
+    .union(serenity::Permissions::DEAFEN_MEMBERS)
+    .union(serenity::Permissions::MOVE_MEMBERS)
+    .union(serenity::Permissions::VIEW_CHANNEL)
+    .union(serenity::Permissions::CONNECT)
+    .union(serenity::Permissions::SPEAK);
+
+pub fn panel_message() -> serenity::CreateMessage {
+    let embed = serenity::CreateEmbed::new()
+        .title("Panneau de contrôle du salon")
+        .description("Seul le propriétaire du salon peut utiliser les contrôles ci-dessous.")
+        .color(0x5865F2)
+        .footer(serenity::CreateEmbedFooter::new("VECTOR ©"));
+
+    let first_row = vec![
+        serenity::CreateButton::new("voice_panel_lock").label("Verrouiller").emoji('🔒').style(serenity::ButtonStyle::Secondary),
+        serenity::CreateButton::new("voice_panel_unlock").label("Déverrouiller").emoji('🔓').style(serenity::ButtonStyle::Secondary),
+        serenity::CreateButton::new("voice_panel_hide").label("Masquer").emoji('🙈').style(serenity::ButtonStyle::Secondary),
+        serenity::CreateButton::new("voice_panel_show").label("Afficher").emoji('👁').style(serenity::ButtonStyle::Secondary),
+    ];
+
+    let second_row = vec![
+        serenity::CreateButton::new("voice_panel_limit").label("Limite").emoji('👥').style(serenity::ButtonStyle::Primary),
+        serenity::CreateButton::new("voice_panel_rename").label("Renommer").emoji('✏').style(serenity::ButtonStyle::Primary),
+        serenity::CreateButton::new("voice_panel_bitrate").label("Débit").emoji('🎚').style(serenity::ButtonStyle::Primary),
+        serenity::CreateButton::new("voice_panel_waiting").label("Salle d'attente").emoji('⏳').style(serenity::ButtonStyle::Primary),
+    ];
+
+    let kick_select = serenity::CreateSelectMenu::new("voice_panel_kick", serenity::CreateSelectMenuKind::User { default_users: None })
+        .placeholder("Expulser un membre du salon");
+    let ban_select = serenity::CreateSelectMenu::new("voice_panel_ban", serenity::CreateSelectMenuKind::User { default_users: None })
+        .placeholder("Bannir un membre du salon");
+    let transfer_select = serenity::CreateSelectMenu::new("voice_panel_transfer", serenity::CreateSelectMenuKind::User { default_users: None })
+        .placeholder("Transférer la propriété");
+
+    serenity::CreateMessage::new()
+        .embed(embed)
+        .components(vec![
+            serenity::CreateActionRow::Buttons(first_row),
+            serenity::CreateActionRow::Buttons(second_row),
+            serenity::CreateActionRow::SelectMenu(kick_select),
+            serenity::CreateActionRow::SelectMenu(ban_select),
+            serenity::CreateActionRow::SelectMenu(transfer_select),
+        ])
+}
+
+pub async fn channel_owner(
+    db: &sqlx::Pool<sqlx::Sqlite>,
+    channel_id: serenity::ChannelId,
+) -> Result<Option<serenity::UserId>, crate::Error> {
+    let owner_id: Option<i64> = sqlx::query_scalar(
+        "SELECT owner_id FROM voice_channels WHERE channel_id = ?"
+    )
+    .bind(channel_id.get() as i64)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(owner_id.map(|id| serenity::UserId::new(id as u64)))
+}
+
+pub fn member_voice_channel(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+) -> Option<serenity::ChannelId> {
+    ctx.cache.guild(guild_id)?.voice_states.get(&user_id)?.channel_id
+}
+
+async fn edit_everyone_overwrite(
+    ctx: &serenity::Context,
+    channel_id: serenity::ChannelId,
+    edit: impl FnOnce(&mut serenity::Permissions, &mut serenity::Permissions),
+) -> Result<(), crate::Error> {
+    let channel = channel_id.to_channel(ctx).await?.guild().ok_or("Salon introuvable")?;
+    let everyone = serenity::PermissionOverwriteType::Role(serenity::RoleId::new(channel.guild_id.get()));
+
+    let (mut allow, mut deny) = channel.permission_overwrites.iter()
+        .find(|o| o.kind == everyone)
+        .map(|o| (o.allow, o.deny))
+        .unwrap_or_default();
+
+    edit(&mut allow, &mut deny);
+
+    channel_id.create_permission(ctx, serenity::PermissionOverwrite { allow, deny, kind: everyone }).await?;
+    Ok(())
+}
+
+pub async fn set_locked(ctx: &serenity::Context, channel_id: serenity::ChannelId, locked: bool) -> Result<(), crate::Error> {
+    edit_everyone_overwrite(ctx, channel_id, |allow, deny| {
+        allow.set(serenity::Permissions::CONNECT, !locked);
+        deny.set(serenity::Permissions::CONNECT, locked);
+    }).await
+}
+
+pub async fn set_hidden(ctx: &serenity::Context, channel_id: serenity::ChannelId, hidden: bool) -> Result<(), crate::Error> {
+    edit_everyone_overwrite(ctx, channel_id, |_, deny| {
+        deny.set(serenity::Permissions::VIEW_CHANNEL, hidden);
+    }).await
+}
+
+pub async fn set_user_limit(ctx: &serenity::Context, channel_id: serenity::ChannelId, limit: u32) -> Result<(), crate::Error> {
+    channel_id.edit(ctx, serenity::EditChannel::new().user_limit(limit.min(99))).await?;
+    Ok(())
+}
+
+pub async fn rename(ctx: &serenity::Context, channel_id: serenity::ChannelId, name: &str) -> Result<(), crate::Error> {
+    channel_id.edit(ctx, serenity::EditChannel::new().name(name)).await?;
+    Ok(())
+}
+
+pub fn max_bitrate_kbps(ctx: &serenity::Context, guild_id: serenity::GuildId) -> u32 {
+    let tier = ctx.cache.guild(guild_id).map(|g| g.premium_tier).unwrap_or_default();
+    match tier {
+        serenity::PremiumTier::Tier1 => 128,
+        serenity::PremiumTier::Tier2 => 256,
+        serenity::PremiumTier::Tier3 => 384,
+        _ => 96,
+    }
+}
+
+pub async fn set_bitrate(ctx: &serenity::Context, channel_id: serenity::ChannelId, kbps: u32) -> Result<(), crate::Error> {
+    channel_id.edit(ctx, serenity::EditChannel::new().bitrate(kbps * 1000)).await?;
+    Ok(())
+}
+
+pub async fn permit_member(ctx: &serenity::Context, channel_id: serenity::ChannelId, user_id: serenity::UserId) -> Result<(), crate::Error> {
+    channel_id.create_permission(ctx, serenity::PermissionOverwrite {
+        allow: serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::CONNECT,
+        deny: serenity::Permissions::empty(),
+        kind: serenity::PermissionOverwriteType::Member(user_id),
+    }).await?;
+    Ok(())
+}
+
+pub async fn reject_member(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    user_id: serenity::UserId,
+) -> Result<(), crate::Error> {
+    channel_id.create_permission(ctx, serenity::PermissionOverwrite {
+        allow: serenity::Permissions::empty(),
+        deny: serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::CONNECT,
+        kind: serenity::PermissionOverwriteType::Member(user_id),
+    }).await?;
+
+    kick_member(ctx, guild_id, channel_id, user_id).await?;
+    Ok(())
+}
+
+pub async fn kick_member(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    user_id: serenity::UserId,
+) -> Result<bool, crate::Error> {
+    if member_voice_channel(ctx, guild_id, user_id) != Some(channel_id) {
+        return Ok(false);
+    }
+
+    guild_id.disconnect_member(ctx, user_id).await?;
+    Ok(true)
+}
+
+async fn reply(
+    ctx: &serenity::Context,
+    component: &serenity::ComponentInteraction,
+    content: impl Into<String>,
+) -> Result<(), crate::Error> {
+    component.create_response(ctx, serenity::CreateInteractionResponse::Message(
+        serenity::CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true)
+    )).await?;
+    Ok(())
+}
+
+fn text_modal(custom_id: &str, title: &str, label: &str, placeholder: &str) -> serenity::CreateModal {
+    serenity::CreateModal::new(custom_id, title)
+        .components(vec![
+            serenity::CreateActionRow::InputText(serenity::CreateInputText::new(
+                serenity::InputTextStyle::Short, label, "value"
+            ).placeholder(placeholder).max_length(100)),
+        ])
+}
+
+pub async fn handle_component(
+    ctx: &serenity::Context,
+    component: &serenity::ComponentInteraction,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let custom_id = component.data.custom_id.as_str();
+    let channel_id = component.channel_id;
+    let Some(guild_id) = component.guild_id else {
+        return Ok(());
+    };
+
+    let _guard = data.voice_locks.lock(channel_id.get()).await;
+
+    let Some(owner_id) = channel_owner(&data.db, channel_id).await? else {
+        return reply(ctx, component, "Ce salon n'est plus un salon temporaire.").await;
+    };
+
+    if component.user.id != owner_id {
+        return reply(ctx, component, "Seul le propriétaire du salon peut utiliser ce panneau.").await;
+    }
+
+    if let Some((action, target)) = custom_id.strip_prefix("voice_panel_wait_").and_then(|rest| rest.split_once(':')) {
+        let Ok(target) = target.parse::<u64>() else { return Ok(()) };
+        return waiting::handle_decision(ctx, component, guild_id, serenity::UserId::new(target), action == "accept", data).await;
+    }
+
+    let selected_user = match &component.data.kind {
+        serenity::ComponentInteractionDataKind::UserSelect { values } => values.first().copied(),
+        _ => None,
+    };
+
+    match custom_id {
+        "voice_panel_lock" => {
+            set_locked(ctx, channel_id, true).await?;
+            preferences::save_locked(&data.db, owner_id, true).await?;
+            reply(ctx, component, "🔒 Salon verrouillé.").await?;
+        }
+        "voice_panel_unlock" => {
+            set_locked(ctx, channel_id, false).await?;
+            preferences::save_locked(&data.db, owner_id, false).await?;
+            reply(ctx, component, "🔓 Salon déverrouillé.").await?;
+        }
+        "voice_panel_hide" => {
+            set_hidden(ctx, channel_id, true).await?;
+            reply(ctx, component, "🙈 Salon masqué.").await?;
+        }
+        "voice_panel_show" => {
+            set_hidden(ctx, channel_id, false).await?;
+            reply(ctx, component, "👁️ Salon visible.").await?;
+        }
+        "voice_panel_waiting" => {
+            if waiting::disable(ctx, channel_id, data).await? {
+                reply(ctx, component, "⏳ Salle d'attente désactivée.").await?;
+            } else {
+                let waiting_id = waiting::enable(ctx, channel_id, data).await?;
+                preferences::save_locked(&data.db, owner_id, true).await?;
+                reply(ctx, component, format!("⏳ Salle d'attente activée : <#{}>. Votre salon est verrouillé.", waiting_id)).await?;
+            }
+        }
+        "voice_panel_limit" => {
+            component.create_response(ctx, serenity::CreateInteractionResponse::Modal(
+                text_modal("voice_panel_modal_limit", "Limite d'utilisateurs", "Nombre maximum (0 = illimité)", "0 - 99")
+            )).await?;
+        }
+        "voice_panel_rename" => {
+            component.create_response(ctx, serenity::CreateInteractionResponse::Modal(
+                text_modal("voice_panel_modal_rename", "Renommer le salon", "Nouveau nom", "Mon salon")
+            )).await?;
+        }
+        "voice_panel_bitrate" => {
+            let max = max_bitrate_kbps(ctx, guild_id);
+            component.create_response(ctx, serenity::CreateInteractionResponse::Modal(
+                text_modal("voice_panel_modal_bitrate", "Débit audio", "Débit en kbps", &format!("8 - {}", max))
+            )).await?;
+        }
+        "voice_panel_kick" => {
+            let Some(target) = selected_user else { return Ok(()) };
+            if target == owner_id {
+                return reply(ctx, component, "Vous ne pouvez pas vous expulser vous-même.").await;
+            }
+            if kick_member(ctx, guild_id, channel_id, target).await? {
+                reply(ctx, component, format!("<@{}> a été expulsé du salon.", target)).await?;
+            } else {
+                reply(ctx, component, format!("<@{}> n'est pas dans votre salon.", target)).await?;
+            }
+        }
+        "voice_panel_ban" => {
+            let Some(target) = selected_user else { return Ok(()) };
+            if target == owner_id || target == ctx.cache.current_user().id {
+                return reply(ctx, component, "Vous ne pouvez pas bannir ce membre.").await;
+            }
+            reject_member(ctx, guild_id, channel_id, target).await?;
+            preferences::save_member(&data.db, owner_id, target, false).await?;
+            reply(ctx, component, format!("<@{}> est banni de votre salon.", target)).await?;
+        }
+        "voice_panel_transfer" => {
+            let Some(target) = selected_user else { return Ok(()) };
+            let is_human = ctx.cache.user(target).map(|u| !u.bot).unwrap_or(false);
+            if target == owner_id || !is_human || member_voice_channel(ctx, guild_id, target) != Some(channel_id) {
+                return reply(ctx, component, "Le nouveau propriétaire doit être un membre présent dans votre salon.").await;
+            }
+            crate::voice_system::events::transfer_ownership(ctx, channel_id, target, data).await?;
+            reply(ctx, component, format!("La propriété du salon a été transférée à <@{}>.", target)).await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+pub async fn handle_modal(
+    ctx: &serenity::Context,
+    modal: &serenity::ModalInteraction,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let channel_id = modal.channel_id;
+    let Some(guild_id) = modal.guild_id else {
+        return Ok(());
+    };
+
+    let _guard = data.voice_locks.lock(channel_id.get()).await;
+
+    if channel_owner(&data.db, channel_id).await? != Some(modal.user.id) {
+        modal.create_response(ctx, serenity::CreateInteractionResponse::Message(
+            serenity::CreateInteractionResponseMessage::new()
+                .content("Seul le propriétaire du salon peut utiliser ce panneau.")
+                .ephemeral(true)
+        )).await?;
+        return Ok(());
+    }
+
+    let value = modal.data.components.iter()
+        .filter_map(|row| match row.components.first() {
+            Some(serenity::ActionRowComponent::InputText(input)) => input.value.clone(),
+            _ => None,
+        })
+        .next()
+        .unwrap_or_default();
+    let value = value.trim();
+
+    let content = match modal.data.custom_id.as_str() {
+        "voice_panel_modal_limit" => match value.parse::<u32>() {
+            Ok(limit) if limit <= 99 => {
+                set_user_limit(ctx, channel_id, limit).await?;
+                preferences::save_user_limit(&data.db, modal.user.id, limit).await?;
+                if limit == 0 { "Limite retirée.".to_string() } else { format!("Limite fixée à {} membres.", limit) }
+            }
+            _ => "La limite doit être un nombre entre 0 et 99.".to_string(),
+        },
+        "voice_panel_modal_rename" => {
+            if value.is_empty() {
+                "Le nom ne peut pas être vide.".to_string()
+            } else {
+                rename(ctx, channel_id, value).await?;
+                preferences::save_name(&data.db, modal.user.id, value).await?;
+                format!("Salon renommé en **{}**.", value)
+            }
+        }
+        "voice_panel_modal_bitrate" => {
+            let max = max_bitrate_kbps(ctx, guild_id);
+            match value.trim_end_matches("kbps").trim().parse::<u32>() {
+                Ok(kbps) if (8..=max).contains(&kbps) => {
+                    set_bitrate(ctx, channel_id, kbps).await?;
+                    preferences::save_bitrate(&data.db, modal.user.id, kbps).await?;
+                    format!("Débit fixé à {} kbps.", kbps)
+                }
+                _ => format!("Le débit doit être compris entre 8 et {} kbps.", max),
+            }
+        }
+        _ => return Ok(()),
+    };
+
+    modal.create_response(ctx, serenity::CreateInteractionResponse::Message(
+        serenity::CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true)
+    )).await?;
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use poise::serenity_prelude as serenity;
+use sqlx::{FromRow, Pool, Sqlite};
+
+#[derive(Debug, Clone, Default, FromRow)]
+pub struct VoicePreferences {
+    pub name_template: Option<String>,
+    pub user_limit: Option<i64>,
+    pub bitrate: Option<i64>,
+    pub locked: bool,
+}
+
+pub fn render_name(template: &str, user: &serenity::User, number: i64) -> String {
+    template
+        .replace("{name}", &user.name)
+        .replace("{count}", &number.to_string())
+}
+
+pub async fn load(db: &Pool<Sqlite>, user_id: serenity::UserId) -> Result<VoicePreferences, sqlx::Error> {
+    let preferences: Option<VoicePreferences> = sqlx::query_as(
+        "SELECT name_template, user_limit, bitrate, locked FROM voice_preferences WHERE user_id = ?"
+    )
+    .bind(user_id.get() as i64)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(preferences.unwrap_or_default())
+}
+
+pub async fn load_members(db: &Pool<Sqlite>, owner_id: serenity::UserId) -> Result<Vec<(serenity::UserId, bool)>, sqlx::Error> {
+    let rows: Vec<(i64, bool)> = sqlx::query_as(
+        "SELECT user_id, allowed FROM voice_preference_members WHERE owner_id = ?"
+    )
+    .bind(owner_id.get() as i64)
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().map(|(id, allowed)| (serenity::UserId::new(id as u64), allowed)).collect())
+}
+
+async fn save_column(db: &Pool<Sqlite>, user_id: serenity::UserId, column: &str, value: Option<i64>) -> Result<(), sqlx::Error> {
+    sqlx::query(&format!(
+        "INSERT INTO voice_preferences (user_id, {column}) VALUES (?, ?)
+         ON CONFLICT(user_id) DO UPDATE SET {column} = excluded.{column}"
+    ))
+    .bind(user_id.get() as i64)
+    .bind(value)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+pub async fn save_name(db: &Pool<Sqlite>, user_id: serenity::UserId, name: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO voice_preferences (user_id, name_template) VALUES (?, ?)
+         ON CONFLICT(user_id) DO UPDATE SET name_template = excluded.name_template"
+    )
+    .bind(user_id.get() as i64)
+    .bind(name)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+pub async fn save_user_limit(db: &Pool<Sqlite>, user_id: serenity::UserId, limit: u32) -> Result<(), sqlx::Error> {
+    save_column(db, user_id, "user_limit", Some(limit as i64)).await
+}
+
+pub async fn save_bitrate(db: &Pool<Sqlite>, user_id: serenity::UserId, kbps: u32) -> Result<(), sqlx::Error> {
+    save_column(db, user_id, "bitrate", Some(kbps as i64)).await
+}
+
+pub async fn save_locked(db: &Pool<Sqlite>, user_id: serenity::UserId, locked: bool) -> Result<(), sqlx::Error> {
+    save_column(db, user_id, "locked", Some(locked as i64)).await
+}
+
+pub async fn save_member(db: &Pool<Sqlite>, owner_id: serenity::UserId, user_id: serenity::UserId, allowed: bool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO voice_preference_members (owner_id, user_id, allowed) VALUES (?, ?, ?)
+         ON CONFLICT(owner_id, user_id) DO UPDATE SET allowed = excluded.allowed"
+    )
+    .bind(owner_id.get() as i64)
+    .bind(user_id.get() as i64)
+    .bind(allowed)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+pub async fn reset(db: &Pool<Sqlite>, user_id: serenity::UserId) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM voice_preferences WHERE user_id = ?")
+        .bind(user_id.get() as i64)
+        .execute(db)
+        .await?;
+
+    sqlx::query("DELETE FROM voice_preference_members WHERE owner_id = ?")
+        .bind(user_id.get() as i64)
+        .execute(db)
+        .await?;
+
+    Ok(())
+}
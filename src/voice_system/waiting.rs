@@ -0,0 +1,229 @@
+use poise::serenity_prelude as serenity;
+use crate::Data;
+use crate::voice_system::{panel, preferences};
+
+pub async fn waiting_channel(
+    db: &sqlx::Pool<sqlx::Sqlite>,
+    room_id: serenity::ChannelId,
+) -> Result<Option<serenity::ChannelId>, crate::Error> {
+    let waiting_id: Option<Option<i64>> = sqlx::query_scalar(
+        "SELECT waiting_channel_id FROM voice_channels WHERE channel_id = ?"
+    )
+    .bind(room_id.get() as i64)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(waiting_id.flatten().map(|id| serenity::ChannelId::new(id as u64)))
+}
+
+pub async fn room_for_waiting_channel(
+    db: &sqlx::Pool<sqlx::Sqlite>,
+    waiting_id: serenity::ChannelId,
+) -> Result<Option<(serenity::ChannelId, serenity::UserId)>, crate::Error> {
+    let room: Option<(i64, i64)> = sqlx::query_as(
+        "SELECT channel_id, owner_id FROM voice_channels WHERE waiting_channel_id = ?"
+    )
+    .bind(waiting_id.get() as i64)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(room.map(|(channel_id, owner_id)| (serenity::ChannelId::new(channel_id as u64), serenity::UserId::new(owner_id as u64))))
+}
+
+pub async fn enable(
+    ctx: &serenity::Context,
+    room_id: serenity::ChannelId,
+    data: &Data,
+) -> Result<serenity::ChannelId, crate::Error> {
+    if let Some(existing) = waiting_channel(&data.db, room_id).await? {
+        return Ok(existing);
+    }
+
+    let room = room_id.to_channel(ctx).await?.guild().ok_or("Salon introuvable")?;
+    let guild_id = room.guild_id;
+
+    let name: String = format!("⏳〢Attente {}", room.name).chars().take(100).collect();
+    let permissions = vec![
+        serenity::PermissionOverwrite {
+            allow: serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::CONNECT,
+            deny: serenity::Permissions::SPEAK,
+            kind: serenity::PermissionOverwriteType::Role(serenity::RoleId::new(guild_id.get())),
+        },
+        serenity::PermissionOverwrite {
+            allow: serenity::Permissions::all(),
+            deny: serenity::Permissions::empty(),
+            kind: serenity::PermissionOverwriteType::Member(ctx.cache.current_user().id),
+        },
+    ];
+
+    let mut builder = serenity::CreateChannel::new(name)
+        .kind(serenity::ChannelType::Voice)
+        .permissions(permissions);
+
+    if let Some(parent_id) = room.parent_id {
+        builder = builder.category(parent_id);
+    }
+    if let Some(position) = room.position.checked_add(1) {
+        builder = builder.position(position);
+    }
+
+    let waiting = guild_id.create_channel(ctx, builder).await?;
+
+    sqlx::query("UPDATE voice_channels SET waiting_channel_id = ? WHERE channel_id = ?")
+        .bind(waiting.id.get() as i64)
+        .bind(room_id.get() as i64)
+        .execute(&data.db)
+        .await?;
+
+    panel::set_locked(ctx, room_id, true).await?;
+
+    Ok(waiting.id)
+}
+
+pub async fn disable(
+    ctx: &serenity::Context,
+    room_id: serenity::ChannelId,
+    data: &Data,
+) -> Result<bool, crate::Error> {
+    let Some(waiting_id) = waiting_channel(&data.db, room_id).await? else {
+        return Ok(false);
+    };
+
+    sqlx::query("UPDATE voice_channels SET waiting_channel_id = NULL WHERE channel_id = ?")
+        .bind(room_id.get() as i64)
+        .execute(&data.db)
+        .await?;
+
+    let _ = waiting_id.delete(ctx).await;
+
+    Ok(true)
+}
+
+pub async fn cleanup_room(
+    ctx: &serenity::Context,
+    room_id: serenity::ChannelId,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    disable(ctx, room_id, data).await?;
+
+    sqlx::query("DELETE FROM voice_temp_permits WHERE channel_id = ?")
+        .bind(room_id.get() as i64)
+        .execute(&data.db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn prompt_owner(
+    ctx: &serenity::Context,
+    room_id: serenity::ChannelId,
+    owner_id: serenity::UserId,
+    user_id: serenity::UserId,
+) -> Result<(), crate::Error> {
+    let buttons = vec![
+        serenity::CreateButton::new(format!("voice_panel_wait_accept:{}", user_id)).label("Accepter").style(serenity::ButtonStyle::Success),
+        serenity::CreateButton::new(format!("voice_panel_wait_deny:{}", user_id)).label("Refuser").style(serenity::ButtonStyle::Danger),
+    ];
+
+    room_id.send_message(ctx, serenity::CreateMessage::new()
+        .content(format!("<@{}>", owner_id))
+        .embed(serenity::CreateEmbed::new()
+            .title("Salle d'attente")
+            .description(format!("<@{}> souhaite rejoindre votre salon.", user_id))
+            .color(0xf1c40f)
+            .timestamp(serenity::Timestamp::now()))
+        .components(vec![serenity::CreateActionRow::Buttons(buttons)])
+    ).await?;
+
+    Ok(())
+}
+
+pub async fn handle_decision(
+    ctx: &serenity::Context,
+    component: &serenity::ComponentInteraction,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+    accept: bool,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let room_id = component.channel_id;
+    let waiting_id = waiting_channel(&data.db, room_id).await?;
+    let still_waiting = waiting_id.is_some() && panel::member_voice_channel(ctx, guild_id, user_id) == waiting_id;
+
+    let outcome = if !still_waiting {
+        format!("<@{}> n'est plus dans la salle d'attente.", user_id)
+    } else if user_id == component.user.id {
+        "Vous êtes le propriétaire de ce salon.".to_string()
+    } else if accept {
+        panel::permit_member(ctx, room_id, user_id).await?;
+
+        sqlx::query("INSERT OR IGNORE INTO voice_temp_permits (channel_id, user_id) VALUES (?, ?)")
+            .bind(room_id.get() as i64)
+            .bind(user_id.get() as i64)
+            .execute(&data.db)
+            .await?;
+
+        guild_id.move_member(ctx, user_id, room_id).await?;
+        format!("✅ <@{}> a été accepté dans le salon.", user_id)
+    } else {
+        guild_id.disconnect_member(ctx, user_id).await?;
+        format!("❌ <@{}> a été refusé.", user_id)
+    };
+
+    component.create_response(ctx, serenity::CreateInteractionResponse::UpdateMessage(
+        serenity::CreateInteractionResponseMessage::new()
+            .content("")
+            .embed(serenity::CreateEmbed::new()
+                .title("Salle d'attente")
+                .description(outcome)
+                .color(0x95a5a6))
+            .components(vec![])
+    )).await?;
+
+    Ok(())
+}
+
+pub async fn forget_temporary_permit(
+    db: &sqlx::Pool<sqlx::Sqlite>,
+    room_id: serenity::ChannelId,
+    user_id: serenity::UserId,
+) -> Result<(), crate::Error> {
+    sqlx::query("DELETE FROM voice_temp_permits WHERE channel_id = ? AND user_id = ?")
+        .bind(room_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .execute(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn revoke_temporary_permit(
+    ctx: &serenity::Context,
+    room_id: serenity::ChannelId,
+    user_id: serenity::UserId,
+    data: &Data,
+) -> Result<(), crate::Error> {
+    let result = sqlx::query("DELETE FROM voice_temp_permits WHERE channel_id = ? AND user_id = ?")
+        .bind(room_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .execute(&data.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(());
+    }
+
+    let permanent = match panel::channel_owner(&data.db, room_id).await? {
+        Some(owner_id) => preferences::load_members(&data.db, owner_id).await?
+            .contains(&(user_id, true)),
+        None => false,
+    };
+
+    if permanent {
+        panel::permit_member(ctx, room_id, user_id).await?;
+    } else {
+        let _ = room_id.delete_permission(ctx, serenity::PermissionOverwriteType::Member(user_id)).await;
+    }
+
+    Ok(())
+}